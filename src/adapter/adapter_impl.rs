@@ -1,16 +1,25 @@
-use std::sync::{Arc, OnceLock};
+use std::{cell::{OnceCell, RefCell}, sync::{Arc, OnceLock}};
 
 use trustfall::{FieldValue, Schema, provider::{AsVertex, ContextIterator, ContextOutcomeIterator, EdgeParameters, ResolveEdgeInfo, ResolveInfo, Typename, VertexIterator, resolve_coercion_using_schema, resolve_property_with}};
 
+use rustc_data_structures::fx::FxHashMap;
 use rustc_interface::Queries;
+use rustc_middle::{mir::{interpret::AllocId, mono::{MonoItem, MonoItemData}}, ty::TyCtxt};
+use rustc_span::{def_id::DefId, Span, Symbol};
 
-use super::{vertex::Vertex, util::is_subtype};
+use super::{vertex::Vertex, util::{is_subtype, mono_items, span_to_string}};
 
 static SCHEMA: OnceLock<Schema> = OnceLock::new();
 
 #[non_exhaustive]
 pub struct Adapter<'a> {
-    pub(crate) queries: &'a Queries<'a>
+    pub(crate) queries: &'a Queries<'a>,
+    /// The allocations the initial values of statics are copied to, see [`eval_const_item`](super::util::eval_const_item).
+    pub(super) static_allocs: RefCell<FxHashMap<DefId, AllocId>>,
+    /// The items codegen instantiates, which `Instance` vertices are indices into.
+    mono_items: OnceCell<Vec<(MonoItem<'a>, MonoItemData, Symbol)>>,
+    /// The spans `span` properties output, by what they output, see [`Adapter::span`].
+    spans: RefCell<FxHashMap<String, Span>>,
 }
 
 impl<'a> Adapter<'a> {
@@ -24,7 +33,26 @@ impl<'a> Adapter<'a> {
     }
 
     pub fn new(queries: &'a Queries<'a>) -> Self {
-        Self { queries }
+        Self { queries, static_allocs: Default::default(), mono_items: OnceCell::new(), spans: Default::default() }
+    }
+
+    /// The items codegen instantiates, see [`mono_items`], collected the first time they're asked for.
+    pub(super) fn mono_items(&self, ctxt: TyCtxt<'a>) -> &[(MonoItem<'a>, MonoItemData, Symbol)] {
+        self.mono_items.get_or_init(|| mono_items(ctxt))
+    }
+
+    /// `span` in the `file:line:col: line:col` format `span` properties output,
+    /// remembering which span that was for [`Adapter::span`].
+    pub(super) fn span_to_string(&self, ctxt: TyCtxt<'_>, span: Span) -> String {
+        let string = span_to_string(ctxt, span);
+        self.spans.borrow_mut().entry(string.clone()).or_insert(span);
+        string
+    }
+
+    /// The span a `span` property of this adapter output as `span`, so that it can be pointed
+    /// at without parsing the file name and position back out of it.
+    pub(crate) fn span(&self, span: &str) -> Option<Span> {
+        self.spans.borrow().get(span).copied()
     }
 }
 
@@ -34,11 +62,35 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
     fn resolve_starting_vertices(
         &self,
         edge_name: &Arc<str>,
-        _parameters: &EdgeParameters,
+        parameters: &EdgeParameters,
         resolve_info: &ResolveInfo,
     ) -> VertexIterator<'a, Self::Vertex> {
+        let str_parameter = |name: &str| {
+            parameters
+                .get(name)
+                .and_then(FieldValue::as_str)
+                .unwrap_or_else(|| panic!("{edge_name} requires a String! {name}"))
+        };
+        let int_parameter = |name: &str| {
+            parameters
+                .get(name)
+                .and_then(FieldValue::as_i64)
+                .unwrap_or_else(|| panic!("{edge_name} requires an Int! {name}"))
+        };
         match edge_name.as_ref() {
             "Crate" => super::entrypoints::crate_(resolve_info),
+            "DefByPath" => super::entrypoints::def_by_path(str_parameter("path"), resolve_info, self),
+            "DiagnosticItem" => super::entrypoints::diagnostic_item(str_parameter("name"), resolve_info, self),
+            "FnByPath" => super::entrypoints::fn_by_path(str_parameter("path"), resolve_info, self),
+            "ItemByName" => super::entrypoints::item_by_name(str_parameter("name"), resolve_info, self),
+            "LangItem" => super::entrypoints::lang_item(str_parameter("name"), resolve_info, self),
+            "NodeAt" => super::entrypoints::node_at(
+                str_parameter("file"),
+                usize::try_from(int_parameter("line")).unwrap_or(0),
+                usize::try_from(int_parameter("col")).unwrap_or(0),
+                resolve_info,
+                self,
+            ),
             _ => {
                 unreachable!(
                     "attempted to resolve starting vertices for unexpected edge name: {edge_name}"
@@ -56,22 +108,123 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
     ) -> ContextOutcomeIterator<'a, V, FieldValue> {
         if property_name.as_ref() == "__typename" {
             return resolve_property_with(contexts, |vertex| vertex.typename().into());
-        } else if is_subtype(Adapter::schema(), "Node", type_name) {
-            match property_name.as_ref() {
-                "inside_const_context" => {
-                    return super::properties::resolve_inside_const_context_property(
-                        contexts,
-                        property_name.as_ref(),
-                        resolve_info,
-                        self,
-                    );
-                }
-                _ => {}
-            }
+        } else if is_subtype(Adapter::schema(), "Node", type_name)
+            && property_name.as_ref() == "inside_const_context"
+        {
+            return super::properties::resolve_inside_const_context_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+                self,
+            );
         }
         match type_name.as_ref() {
-            "ConstStability" => {
-                super::properties::resolve_const_stability_property(
+            "AssignOp" => {
+                super::properties::resolve_assign_op_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Attribute" => {
+                super::properties::resolve_attribute_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "AsyncBlock" => {
+                super::properties::resolve_async_block_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "BasicBlock" => {
+                super::properties::resolve_basic_block_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Block" => {
+                super::properties::resolve_block_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Binary" => {
+                super::properties::resolve_binary_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Borrow" => {
+                super::properties::resolve_borrow_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "BorrowAdjustment" => {
+                super::properties::resolve_borrow_adjustment_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Const" => {
+                super::properties::resolve_const_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "ConstValue" => {
+                super::properties::resolve_const_value_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "CoroutineLayout" => {
+                super::properties::resolve_coroutine_layout_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "CoroutineSavedLocal" => {
+                super::properties::resolve_coroutine_saved_local_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Crate" => {
+                super::properties::resolve_crate_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "DerefAdjustment" => {
+                super::properties::resolve_deref_adjustment_property(
                     contexts,
                     property_name.as_ref(),
                     resolve_info,
@@ -84,6 +237,30 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                 resolve_info,
                 self,
             ),
+            "Deprecation" => {
+                super::properties::resolve_deprecation_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "DocLink" => {
+                super::properties::resolve_doc_link_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Feature" => {
+                super::properties::resolve_feature_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
             "Fn" => {
                 super::properties::resolve_fn_property(
                     contexts,
@@ -92,6 +269,30 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
+            "Impl" => {
+                super::properties::resolve_impl_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Index" => {
+                super::properties::resolve_index_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Instance" => {
+                super::properties::resolve_instance_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
             "Item" => {
                 super::properties::resolve_item_property(
                     contexts,
@@ -100,6 +301,54 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
+            "Lit" => {
+                super::properties::resolve_lit_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "MetaItem" => {
+                super::properties::resolve_meta_item_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Mir" => {
+                super::properties::resolve_mir_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "MirLocal" => {
+                super::properties::resolve_mir_local_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "MirStatement" => {
+                super::properties::resolve_mir_statement_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Move" => {
+                super::properties::resolve_move_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
             "Node" => {
                 super::properties::resolve_node_property(
                     contexts,
@@ -108,7 +357,28 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
-            "Stability" => {
+            "PanicSite" => {
+                super::properties::resolve_panic_site_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "PointerAdjustment" => {
+                super::properties::resolve_pointer_adjustment_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Stability"
+            | "StableLevel"
+            | "UnstableLevel"
+            | "ConstStability"
+            | "ConstStableLevel"
+            | "ConstUnstableLevel" => {
                 super::properties::resolve_stability_property(
                     contexts,
                     property_name.as_ref(),
@@ -116,6 +386,69 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
+            "RegionConstraint" => {
+                super::properties::resolve_region_constraint_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Static" => {
+                super::properties::resolve_static_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "ThirArm" => {
+                super::properties::resolve_thir_arm_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "ThirExpr"
+            | "ThirBlock"
+            | "ThirBorrow"
+            | "ThirCall"
+            | "ThirMatch" => {
+                super::properties::resolve_thir_expr_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "ThirPat" => {
+                super::properties::resolve_thir_pat_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "ThirStmt" => {
+                super::properties::resolve_thir_stmt_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "Terminator"
+            | "AssertTerminator"
+            | "CallTerminator"
+            | "DropTerminator" => {
+                super::properties::resolve_terminator_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
             "Ty" => {
                 super::properties::resolve_ty_property(
                     contexts,
@@ -124,6 +457,22 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
+            "Unary" => {
+                super::properties::resolve_unary_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
+            "UnsafeOperation" => {
+                super::properties::resolve_unsafe_operation_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                    self,
+                )
+            }
             _ => {
                 unreachable!(
                     "attempted to read property '{property_name}' on unexpected type: {type_name}"
@@ -140,7 +489,83 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
         parameters: &EdgeParameters,
         resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Self::Vertex>> {
+        if is_subtype(Adapter::schema(), "Node", type_name) && edge_name.as_ref() == "attribute" {
+            return super::edges::resolve_node_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+                self,
+            );
+        }
         match type_name.as_ref() {
+            "Adjustment"
+            | "BorrowAdjustment"
+            | "DynStarAdjustment"
+            | "NeverToAnyAdjustment"
+            | "PointerAdjustment" => {
+                super::edges::resolve_adjustment_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "AssignOp" => {
+                super::edges::resolve_assign_op_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "Attribute" => {
+                super::edges::resolve_attribute_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "AsyncBlock" => {
+                super::edges::resolve_async_block_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "Await" => {
+                super::edges::resolve_await_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "BasicBlock" => {
+                super::edges::resolve_basic_block_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "Binary" => {
+                super::edges::resolve_binary_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
             "Block" => {
                 super::edges::resolve_block_edge(
                     contexts,
@@ -159,6 +584,60 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
+            "Borrow" => {
+                super::edges::resolve_borrow_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "Borrowck" => {
+                super::edges::resolve_borrowck_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "Const" => {
+                super::edges::resolve_const_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "ConstValue" => {
+                super::edges::resolve_const_value_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "CoroutineLayout" => {
+                super::edges::resolve_coroutine_layout_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "CoroutineSavedLocal" => {
+                super::edges::resolve_coroutine_saved_local_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
             "Crate" => {
                 super::edges::resolve_crate_edge(
                     contexts,
@@ -177,6 +656,24 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
+            "DerefAdjustment" => {
+                super::edges::resolve_deref_adjustment_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "DocLink" => {
+                super::edges::resolve_doc_link_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
             "Expr" => {
                 super::edges::resolve_expr_edge(
                     contexts,
@@ -204,7 +701,25 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
-            "Item" => {
+            "Index" => {
+                super::edges::resolve_index_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "Instance" => {
+                super::edges::resolve_instance_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "Item" | "Impl" => {
                 super::edges::resolve_item_edge(
                     contexts,
                     edge_name.as_ref(),
@@ -213,6 +728,15 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
+            "Lit" => {
+                super::edges::resolve_lit_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
             "LocalStatement" => {
                 super::edges::resolve_local_statement_edge(
                     contexts,
@@ -222,6 +746,15 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
+            "MetaItem" => {
+                super::edges::resolve_meta_item_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
             "MethodCall" => {
                 super::edges::resolve_method_call_edge(
                     contexts,
@@ -231,6 +764,33 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
+            "Mir" => {
+                super::edges::resolve_mir_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "MirLocal" => {
+                super::edges::resolve_mir_local_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "Move" => {
+                super::edges::resolve_move_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
             "Node" => {
                 super::edges::resolve_node_edge(
                     contexts,
@@ -240,6 +800,15 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
+            "PanicSite" => {
+                super::edges::resolve_panic_site_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
             "Statement" => {
                 super::edges::resolve_statement_edge(
                     contexts,
@@ -249,6 +818,76 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
+            "Static" => {
+                super::edges::resolve_static_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "Thir" => {
+                super::edges::resolve_thir_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "ThirArm" => {
+                super::edges::resolve_thir_arm_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "ThirExpr"
+            | "ThirBlock"
+            | "ThirBorrow"
+            | "ThirCall"
+            | "ThirMatch" => {
+                super::edges::resolve_thir_expr_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "ThirPat" => {
+                super::edges::resolve_thir_pat_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "ThirStmt" => {
+                super::edges::resolve_thir_stmt_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "Terminator"
+            | "AssertTerminator"
+            | "CallTerminator"
+            | "DropTerminator" => {
+                super::edges::resolve_terminator_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
             "Ty" => {
                 super::edges::resolve_ty_edge(
                     contexts,
@@ -258,6 +897,24 @@ impl<'a, 'b> trustfall::provider::Adapter<'a> for &'a Adapter<'b> {
                     self,
                 )
             }
+            "Unary" => {
+                super::edges::resolve_unary_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
+            "UnsafeOperation" => {
+                super::edges::resolve_unsafe_operation_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                    self,
+                )
+            }
             _ => {
                 unreachable!(
                     "attempted to resolve edge '{edge_name}' on unexpected type: {type_name}"
@@ -1,8 +1,8 @@
-use trustfall::provider::{AsVertex, ContextIterator, ContextOutcomeIterator, EdgeParameters, ResolveEdgeInfo, VertexIterator};
+use trustfall::{FieldValue, provider::{AsVertex, ContextIterator, ContextOutcomeIterator, EdgeParameters, ResolveEdgeInfo, VertexIterator}};
 
 use super::{vertex::Vertex, Adapter};
 
-pub(super) fn resolve_block_edge<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_adjustment_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
@@ -10,84 +10,44 @@ pub(super) fn resolve_block_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "parent" => block::parent(contexts, resolve_info, adapter),
-        "statements" => block::statements(contexts, resolve_info, adapter),
-        "type" => block::type_(contexts, resolve_info, adapter),
+        "target" => adjustment::target(contexts, resolve_info, adapter),
         _ => {
             unreachable!(
-                "attempted to resolve unexpected edge '{edge_name}' on type 'Block'"
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Adjustment'"
             )
         }
     }
 }
 
-mod block {
-    use itertools::Itertools;
-    use rustc_hir::StmtKind;
-    use rustc_hir::ExprKind;
+mod adjustment {
     use trustfall::provider::{
         resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
         ResolveEdgeInfo, VertexIterator,
     };
 
-    use crate::adapter::Adapter;
+    use crate::adapter::{Adapter, vertex::TyOrigin};
 
     use super::super::vertex::Vertex;
 
-    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
-        contexts: ContextIterator<'a, V>,
-        _resolve_info: &ResolveEdgeInfo,
-        adapter: &'a Adapter,
-    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        super::expr::parent(contexts, _resolve_info, adapter)
-    }
-
-    pub(super) fn statements<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn target<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
-        adapter: &'a Adapter,
+        _adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
         resolve_neighbors_with(
             contexts,
             move |vertex| {
-                let hir_id = vertex
-                    .hir_id()
-                    .expect("vertex was not a Node");
-
-                let stmt_ids = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let hir = ctxt.hir();
-                    let expr = hir.expect_expr(hir_id);
-                    let ExprKind::Block(block, ..) = expr.kind else {
-                        unimplemented!("expr was not of type Block: {expr:#?}")
-                    };
-                    block
-                        .stmts
-                        .iter()
-                        .map(|stmt| {
-                            let id = stmt.hir_id;
-                            match stmt.kind {
-                                StmtKind::Local(..) => Vertex::LocalStatement(id),
-                                _ => Vertex::Statement(id),
-                            }
-                        })
-                        .collect_vec()
-                });
+                let (hir_id, index) = vertex
+                    .adjustment()
+                    .expect("vertex was not an Adjustment");
 
-                Box::new(stmt_ids.into_iter())
+                Box::new(std::iter::once(Vertex::Ty(TyOrigin::Adjustment(hir_id, index))))
             },
         )
     }
-
-    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
-        contexts: ContextIterator<'a, V>,
-        _resolve_info: &ResolveEdgeInfo,
-        adapter: &'a Adapter,
-    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        super::expr::type_(contexts, _resolve_info, adapter)
-    }
 }
 
-pub(super) fn resolve_body_edge<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_assign_op_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
@@ -95,19 +55,23 @@ pub(super) fn resolve_body_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "parent" => body::parent(contexts, resolve_info, adapter),
-        "value" => body::value(contexts, resolve_info, adapter),
+        "adjusted_type" => assign_op::adjusted_type(contexts, resolve_info, adapter),
+        "adjustment" => assign_op::adjustment(contexts, resolve_info, adapter),
+        "overloaded_impl_method" => assign_op::overloaded_impl_method(contexts, resolve_info, adapter),
+        "overloaded_method" => assign_op::overloaded_method(contexts, resolve_info, adapter),
+        "parent" => assign_op::parent(contexts, resolve_info, adapter),
+        "type" => assign_op::type_(contexts, resolve_info, adapter),
         _ => {
             unreachable!(
-                "attempted to resolve unexpected edge '{edge_name}' on type 'Body'"
+                "attempted to resolve unexpected edge '{edge_name}' on type 'AssignOp'"
             )
         }
     }
 }
 
-mod body {
+mod assign_op {
     use trustfall::provider::{
-        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        AsVertex, ContextIterator, ContextOutcomeIterator,
         ResolveEdgeInfo, VertexIterator,
     };
 
@@ -115,38 +79,56 @@ mod body {
 
     use super::super::vertex::Vertex;
 
-    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn adjusted_type<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        super::node::parent(contexts, _resolve_info, adapter)
+        super::expr::adjusted_type(contexts, _resolve_info, adapter)
     }
 
-    pub(super) fn value<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn adjustment<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        resolve_neighbors_with(
-            contexts,
-            move |vertex| {
-                let body_id = vertex
-                    .body_id()
-                    .expect("vertex was not a Body");
+        super::expr::adjustment(contexts, _resolve_info, adapter)
+    }
 
-                let hir_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let hir = ctxt.hir();
-                    hir.body(body_id).value.hir_id
-                });
+    pub(super) fn overloaded_impl_method<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::overloaded_impl_method(contexts, _resolve_info, adapter)
+    }
 
-                Box::new(std::iter::once(Vertex::Expr(hir_id)))
-            },
-        )
+    pub(super) fn overloaded_method<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::overloaded_method(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::parent(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::type_(contexts, _resolve_info, adapter)
     }
 }
 
-pub(super) fn resolve_crate_edge<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_async_block_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
@@ -154,20 +136,21 @@ pub(super) fn resolve_crate_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "item" => crate_::item(contexts, resolve_info, adapter),
-        "expr" => crate_::expr(contexts, resolve_info, adapter),
+        "coroutine_layout" => async_block::coroutine_layout(contexts, resolve_info, adapter),
+        "adjusted_type" => async_block::adjusted_type(contexts, resolve_info, adapter),
+        "adjustment" => async_block::adjustment(contexts, resolve_info, adapter),
+        "parent" => async_block::parent(contexts, resolve_info, adapter),
+        "type" => async_block::type_(contexts, resolve_info, adapter),
         _ => {
             unreachable!(
-                "attempted to resolve unexpected edge '{edge_name}' on type 'Crate'"
+                "attempted to resolve unexpected edge '{edge_name}' on type 'AsyncBlock'"
             )
         }
     }
 }
 
-mod crate_ {
-    use itertools::Itertools;
-    use rustc_hir::{ItemKind, intravisit::{Visitor, walk_expr}, ExprKind};
-    use rustc_middle::hir::{nested_filter::OnlyBodies, map::Map};
+mod async_block {
+    use rustc_hir::{ExprKind, Closure, ClosureKind, HirId};
     use trustfall::provider::{
         resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
         ResolveEdgeInfo, VertexIterator,
@@ -177,7 +160,7 @@ mod crate_ {
 
     use super::super::vertex::Vertex;
 
-    pub(super) fn item<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn coroutine_layout<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
@@ -185,152 +168,71 @@ mod crate_ {
         resolve_neighbors_with(
             contexts,
             move |vertex| {
-                let _ = vertex
-                    .as_crate()
-                    .expect("conversion failed, vertex was not a Crate");
-                let items = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let hir = ctxt.hir();
-                    hir
-                        .items()
-                        .map(|id| {
-                            match hir.item(id).kind {
-                                ItemKind::Fn(..) =>  Vertex::Fn(id),
-                                _ => Vertex::Item(id),
-                            }    
-                        })
-                        .collect_vec()
-                });
-                Box::new(items.into_iter())
+                let hir_id = vertex
+                    .hir_id()
+                    .expect("vertex was not a Node");
+
+                coroutine_layout_of(hir_id, adapter)
             },
         )
     }
 
-    #[derive(Clone)]
-    struct AllExprs<'a> {
-        map: Map<'a>,
-        pub exprs: Vec<Vertex>
-    }
-
-    impl<'a> Visitor<'a> for AllExprs<'a> {
-        type NestedFilter = OnlyBodies;
-
-        fn nested_visit_map(&mut self) -> Self::Map {
-            self.map
-        }
-
-        fn visit_expr(&mut self, ex: &'a rustc_hir::Expr<'a>) {
-            let vertex = match ex.kind {
-                ExprKind::MethodCall(..) => Vertex::MethodCall(ex.hir_id),
-                _ => Vertex::Expr(ex.hir_id),
+    /// Resolves to the [`Vertex::CoroutineLayout`] of the coroutine closure
+    /// expression with the given [`HirId`], if it has one.
+    pub(super) fn coroutine_layout_of<'a>(
+        hir_id: HirId,
+        adapter: &'a Adapter,
+    ) -> VertexIterator<'a, Vertex> {
+        let opt_def_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+            let hir = ctxt.hir();
+            let ExprKind::Closure(Closure { def_id, kind: ClosureKind::Coroutine(..), .. }) = hir.expect_expr(hir_id).kind else {
+                return None;
             };
-            self.exprs.push(vertex);
-            walk_expr(self, ex);
+            let def_id = def_id.to_def_id();
+            ctxt.coroutine_layout(def_id).map(|_| def_id)
+        });
+
+        if let Some(def_id) = opt_def_id {
+            Box::new(std::iter::once(Vertex::CoroutineLayout(def_id)))
+        } else {
+            Box::new(std::iter::empty())
         }
     }
 
-    pub(super) fn expr<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn adjusted_type<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        resolve_neighbors_with(
-            contexts,
-            move |vertex| {
-                let _ = vertex
-                    .as_crate()
-                    .expect("conversion failed, vertex was not a Crate");
-                let exprs = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let hir = ctxt.hir();
-                    let mut all_exprs = AllExprs { map: hir, exprs: Vec::new() };
-                    hir.visit_all_item_likes_in_crate(&mut all_exprs);
-                    all_exprs.exprs
-                });
-                Box::new(exprs.into_iter())
-            },
-        )
+        super::expr::adjusted_type(contexts, _resolve_info, adapter)
     }
-}
 
-pub(super) fn resolve_def_edge<'a, V: AsVertex<Vertex> + 'a>(
-    contexts: ContextIterator<'a, V>,
-    edge_name: &str,
-    _parameters: &EdgeParameters,
-    resolve_info: &ResolveEdgeInfo,
-    adapter: &'a Adapter,
-) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-    match edge_name {
-        "stability" => def::stability(contexts, resolve_info, adapter),
-        "const_stability" => def::const_stability(contexts, resolve_info, adapter),
-        _ => {
-            unreachable!(
-                "attempted to resolve unexpected edge '{edge_name}' on type 'Crate'"
-            )
-        }
+    pub(super) fn adjustment<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjustment(contexts, _resolve_info, adapter)
     }
-}
-
-mod def {
-    use trustfall::provider::{
-        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
-        ResolveEdgeInfo, VertexIterator,
-    };
-
-    use crate::adapter::Adapter;
-
-    use super::super::vertex::Vertex;
 
-    pub(super) fn stability<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        resolve_neighbors_with(
-            contexts,
-            move |vertex| {
-                let def_id = vertex
-                    .as_def()
-                    .expect("vertex was not variant 'Def'");
-
-                let stability_exists = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    ctxt.lookup_stability(def_id).is_some()
-                });
-
-                if stability_exists {
-                    Box::new(std::iter::once(Vertex::Stability(*def_id)))
-                } else {
-                    Box::new(std::iter::empty())
-                }
-            },
-        )
+        super::expr::parent(contexts, _resolve_info, adapter)
     }
 
-    pub(super) fn const_stability<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        resolve_neighbors_with(
-            contexts,
-            move |vertex| {
-                let def_id = vertex
-                    .as_def()
-                    .expect("vertex was not variant 'Def'");
-
-                let stability_exists = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    ctxt.lookup_const_stability(def_id).is_some()
-                });
-
-                if stability_exists {
-                    Box::new(std::iter::once(Vertex::ConstStability(*def_id)))
-                } else {
-                    Box::new(std::iter::empty())
-                }
-            },
-        )
+        super::expr::type_(contexts, _resolve_info, adapter)
     }
 }
 
-pub(super) fn resolve_expr_edge<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_attribute_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
@@ -338,17 +240,16 @@ pub(super) fn resolve_expr_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "parent" => expr::parent(contexts, resolve_info, adapter),
-        "type" => expr::type_(contexts, resolve_info, adapter),
+        "meta_item" => attribute::meta_item(contexts, resolve_info, adapter),
         _ => {
             unreachable!(
-                "attempted to resolve unexpected edge '{edge_name}' on type 'Expr'"
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Attribute'"
             )
         }
     }
 }
 
-mod expr {
+mod attribute {
     use trustfall::provider::{
         resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
         ResolveEdgeInfo, VertexIterator,
@@ -358,33 +259,28 @@ mod expr {
 
     use super::super::vertex::Vertex;
 
-    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn meta_item<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
-    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        super::node::parent(contexts, _resolve_info, adapter)
-    }
-
-    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
-        contexts: ContextIterator<'a, V>,
-        _resolve_info: &ResolveEdgeInfo,
-        _adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
         resolve_neighbors_with(
             contexts,
             move |vertex| {
-                let hir_id = vertex
-                    .hir_id()
-                    .expect("conversion failed, vertex was not an Item");
-                
-                Box::new(std::iter::once(Vertex::Ty(hir_id)))
+                let origin = *vertex
+                    .as_attribute()
+                    .expect("conversion failed, vertex was not an Attribute");
+
+                let count = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    origin.attr(ctxt).meta_item_list().map_or(0, |list| list.len())
+                });
+                Box::new((0..count).map(move |index| Vertex::MetaItem(origin, vec![index])))
             },
         )
     }
 }
 
-pub(super) fn resolve_fn_edge<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_await_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
@@ -392,27 +288,31 @@ pub(super) fn resolve_fn_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "body" => fn_::body(contexts, resolve_info, adapter),
-        "parent" => fn_::parent(contexts, resolve_info, adapter),
+        "future" => await_::future(contexts, resolve_info, adapter),
+        "adjusted_type" => await_::adjusted_type(contexts, resolve_info, adapter),
+        "adjustment" => await_::adjustment(contexts, resolve_info, adapter),
+        "parent" => await_::parent(contexts, resolve_info, adapter),
+        "type" => await_::type_(contexts, resolve_info, adapter),
         _ => {
             unreachable!(
-                "attempted to resolve unexpected edge '{edge_name}' on type 'Fn'"
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Await'"
             )
         }
     }
 }
 
-mod fn_ {
+mod await_ {
+    use rustc_hir::ExprKind;
     use trustfall::provider::{
         resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
         ResolveEdgeInfo, VertexIterator,
     };
 
-    use crate::adapter::Adapter;
+    use crate::adapter::{Adapter, vertex::TyOrigin};
 
     use super::super::vertex::Vertex;
 
-    pub(super) fn body<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn future<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
@@ -420,31 +320,58 @@ mod fn_ {
         resolve_neighbors_with(
             contexts,
             move |vertex| {
-                let item_id = vertex
-                    .item_id()
-                    .expect("expected vertex to be an Item");
+                let hir_id = vertex
+                    .as_await()
+                    .expect("vertex was not variant 'Await'");
 
-                let body_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                let future_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
                     let hir = ctxt.hir();
-                    let (.., body_id) = hir.item(item_id).expect_fn();
-                    body_id
+                    let expr = hir.expect_expr(*hir_id);
+                    let ExprKind::Match(into_future, ..) = expr.kind else {
+                        unimplemented!("expr was not an await desugaring: {expr:#?}")
+                    };
+                    into_future.hir_id
                 });
 
-                Box::new(std::iter::once(Vertex::FnBody(body_id)))
+                Box::new(std::iter::once(Vertex::Ty(TyOrigin::Node(future_id))))
             },
         )
     }
 
-    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn adjusted_type<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        super::item::parent(contexts, _resolve_info, adapter)
+        super::expr::adjusted_type(contexts, _resolve_info, adapter)
     }
-}
 
-pub(super) fn resolve_fn_body_edge<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn adjustment<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjustment(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::parent(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::type_(contexts, _resolve_info, adapter)
+    }
+}
+
+pub(super) fn resolve_basic_block_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
@@ -452,19 +379,20 @@ pub(super) fn resolve_fn_body_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "parent" => fn_body::parent(contexts, resolve_info, adapter),
-        "value" => fn_body::value(contexts, resolve_info, adapter),
+        "statement" => basic_block::statement(contexts, resolve_info, adapter),
+        "terminator" => basic_block::terminator(contexts, resolve_info, adapter),
         _ => {
             unreachable!(
-                "attempted to resolve unexpected edge '{edge_name}' on type 'FnBody'"
+                "attempted to resolve unexpected edge '{edge_name}' on type 'BasicBlock'"
             )
         }
     }
 }
 
-mod fn_body {
+mod basic_block {
+    use rustc_middle::mir::Location;
     use trustfall::provider::{
-        AsVertex, ContextIterator, ContextOutcomeIterator,
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
         ResolveEdgeInfo, VertexIterator,
     };
 
@@ -472,24 +400,52 @@ mod fn_body {
 
     use super::super::vertex::Vertex;
 
-    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn statement<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        super::body::parent(contexts, _resolve_info, adapter)
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&origin, &block) = vertex
+                    .as_basic_block()
+                    .expect("conversion failed, vertex was not a BasicBlock");
+
+                let count = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    origin.body(ctxt).basic_blocks[block].statements.len()
+                });
+
+                Box::new((0..count).map(move |statement_index| {
+                    Vertex::MirStatement(origin, Location { block, statement_index })
+                }))
+            },
+        )
     }
 
-    pub(super) fn value<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn terminator<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        super::body::value(contexts, _resolve_info, adapter)
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&origin, &block) = vertex
+                    .as_basic_block()
+                    .expect("conversion failed, vertex was not a BasicBlock");
+
+                let terminator = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    Vertex::from_terminator(ctxt, origin, block)
+                });
+
+                Box::new(std::iter::once(terminator))
+            },
+        )
     }
 }
 
-pub(super) fn resolve_item_edge<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_binary_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
@@ -497,16 +453,21 @@ pub(super) fn resolve_item_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "parent" => item::parent(contexts, resolve_info, adapter),
+        "adjusted_type" => binary::adjusted_type(contexts, resolve_info, adapter),
+        "adjustment" => binary::adjustment(contexts, resolve_info, adapter),
+        "overloaded_impl_method" => binary::overloaded_impl_method(contexts, resolve_info, adapter),
+        "overloaded_method" => binary::overloaded_method(contexts, resolve_info, adapter),
+        "parent" => binary::parent(contexts, resolve_info, adapter),
+        "type" => binary::type_(contexts, resolve_info, adapter),
         _ => {
             unreachable!(
-                "attempted to resolve unexpected edge '{edge_name}' on type 'Item'"
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Binary'"
             )
         }
     }
 }
 
-mod item {
+mod binary {
     use trustfall::provider::{
         AsVertex, ContextIterator, ContextOutcomeIterator,
         ResolveEdgeInfo, VertexIterator,
@@ -516,87 +477,181 @@ mod item {
 
     use super::super::vertex::Vertex;
 
+    pub(super) fn adjusted_type<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjusted_type(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn adjustment<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjustment(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn overloaded_impl_method<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::overloaded_impl_method(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn overloaded_method<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::overloaded_method(contexts, _resolve_info, adapter)
+    }
+
     pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        super::node::parent(contexts, _resolve_info, adapter)
+        super::expr::parent(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::type_(contexts, _resolve_info, adapter)
     }
 }
 
-pub(super) fn resolve_local_statement_edge<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_block_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
     resolve_info: &ResolveEdgeInfo,
-    adapter: &'a Adapter
+    adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "init" => local_statement::init(contexts, resolve_info, adapter),
-        "parent" => local_statement::parent(contexts, resolve_info, adapter),
+        "adjusted_type" => block::adjusted_type(contexts, resolve_info, adapter),
+        "adjustment" => block::adjustment(contexts, resolve_info, adapter),
+        "parent" => block::parent(contexts, resolve_info, adapter),
+        "statements" => block::statements(contexts, resolve_info, adapter),
+        "type" => block::type_(contexts, resolve_info, adapter),
+        "unsafe_operation" => block::unsafe_operation(contexts, resolve_info, adapter),
         _ => {
             unreachable!(
-                "attempted to resolve unexpected edge '{edge_name}' on type 'LocalStatement'"
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Block'"
             )
         }
     }
 }
 
-mod local_statement {
-    use rustc_hir::{StmtKind, Local, intravisit::Map};
+mod block {
+    use itertools::Itertools;
+    use rustc_hir::StmtKind;
+    use rustc_hir::ExprKind;
     use trustfall::provider::{
         resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
         ResolveEdgeInfo, VertexIterator,
     };
 
-    use crate::adapter::Adapter;
+    use crate::adapter::{Adapter, util::unsafe_operations};
 
     use super::super::vertex::Vertex;
 
-    pub(super) fn init<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn adjusted_type<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjusted_type(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn adjustment<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjustment(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::parent(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn statements<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
         resolve_neighbors_with(
             contexts,
-            move |vertex: &Vertex| {
-                let hir_id: rustc_hir::HirId = vertex
+            move |vertex| {
+                let hir_id = vertex
                     .hir_id()
-                    .expect("conversion failed, vertex was not a Node");
+                    .expect("vertex was not a Node");
 
-                let opt_init_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                let stmt_ids = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
                     let hir = ctxt.hir();
-                    let stmt = hir
-                        .find(hir_id)
-                        .expect("LocalStatement couldn't be found")
-                        .expect_stmt();
-                    let StmtKind::Local(Local { init: Some(init_expr), .. }) = stmt.kind else {
-                        return None;
+                    let expr = hir.expect_expr(hir_id);
+                    let ExprKind::Block(block, ..) = expr.kind else {
+                        unimplemented!("expr was not of type Block: {expr:#?}")
                     };
-                    Some(init_expr.hir_id)
+                    block
+                        .stmts
+                        .iter()
+                        .map(|stmt| {
+                            let id = stmt.hir_id;
+                            match stmt.kind {
+                                StmtKind::Local(..) => Vertex::LocalStatement(id),
+                                _ => Vertex::Statement(id),
+                            }
+                        })
+                        .collect_vec()
                 });
 
-                if let Some(init_id) = opt_init_id {
-                    Box::new(std::iter::once(Vertex::Expr(init_id)))
-                } else {
-                    Box::new(std::iter::empty())
-                }
+                Box::new(stmt_ids.into_iter())
             },
         )
     }
 
-    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        super::statement::parent(contexts, _resolve_info, adapter)
+        super::expr::type_(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn unsafe_operation<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .hir_id()
+                    .expect("vertex was not a Node");
+
+                let operations = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    unsafe_operations(ctxt, hir_id)
+                });
+
+                Box::new(operations.into_iter().map(|(def_id, expr)| Vertex::UnsafeOperation(def_id, expr)))
+            },
+        )
     }
 }
 
-pub(super) fn resolve_method_call_edge<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_body_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
@@ -604,19 +659,19 @@ pub(super) fn resolve_method_call_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "parent" => method_call::parent(contexts, resolve_info, adapter),
-        "type" => method_call::type_(contexts, resolve_info, adapter),
+        "parent" => body::parent(contexts, resolve_info, adapter),
+        "value" => body::value(contexts, resolve_info, adapter),
         _ => {
             unreachable!(
-                "attempted to resolve unexpected edge '{edge_name}' on type 'MethodCall'"
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Body'"
             )
         }
     }
 }
 
-mod method_call {
+mod body {
     use trustfall::provider::{
-        AsVertex, ContextIterator, ContextOutcomeIterator,
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
         ResolveEdgeInfo, VertexIterator,
     };
 
@@ -629,19 +684,33 @@ mod method_call {
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        super::expr::parent(contexts, _resolve_info, adapter)
+        super::node::parent(contexts, _resolve_info, adapter)
     }
 
-    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn value<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        super::expr::type_(contexts, _resolve_info, adapter)
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let body_id = vertex
+                    .body_id()
+                    .expect("vertex was not a Body");
+
+                let value = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let hir = ctxt.hir();
+                    Vertex::from_expr(hir.body(body_id).value)
+                });
+
+                Box::new(std::iter::once(value))
+            },
+        )
     }
 }
 
-pub(super) fn resolve_node_edge<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_borrow_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
@@ -649,26 +718,27 @@ pub(super) fn resolve_node_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "parent" => node::parent(contexts, resolve_info, adapter),
+        "local" => borrow::local(contexts, resolve_info, adapter),
+        "statement" => borrow::statement(contexts, resolve_info, adapter),
         _ => {
             unreachable!(
-                "attempted to resolve unexpected edge '{edge_name}' on type 'Node'"
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Borrow'"
             )
         }
     }
 }
 
-mod node {
+mod borrow {
     use trustfall::provider::{
         resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
         ResolveEdgeInfo, VertexIterator,
     };
 
-    use crate::adapter::Adapter;
+    use crate::{adapter::Adapter, query_overrides::borrowck_facts};
 
-    use super::super::vertex::Vertex;
+    use super::super::vertex::{MirOrigin, Vertex};
 
-    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn local<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
@@ -676,25 +746,44 @@ mod node {
         resolve_neighbors_with(
             contexts,
             move |vertex| {
-                let hir_id = vertex
-                    .hir_id()
-                    .expect("conversion failed, vertex was not a Node");
-                let opt_parent_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let hir = ctxt.hir();
-                    hir.opt_parent_id(hir_id)
+                let (&def_id, &borrow) = vertex
+                    .as_borrow()
+                    .expect("conversion failed, vertex was not a Borrow");
+
+                let local = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let facts = borrowck_facts(ctxt, def_id).expect("borrowck facts should have been kept");
+                    facts.borrow_set[borrow].borrowed_place.local
                 });
 
-                if let Some(parent_id) = opt_parent_id {
-                    Box::new(std::iter::once(Vertex::Node(parent_id)))
-                } else {
-                    Box::new(std::iter::empty())
-                }
+                Box::new(std::iter::once(Vertex::MirLocal(MirOrigin::Borrowck(def_id), local)))
+            },
+        )
+    }
+
+    pub(super) fn statement<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&def_id, &borrow) = vertex
+                    .as_borrow()
+                    .expect("conversion failed, vertex was not a Borrow");
+
+                let location = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let facts = borrowck_facts(ctxt, def_id).expect("borrowck facts should have been kept");
+                    facts.borrow_set[borrow].reserve_location
+                });
+
+                Box::new(std::iter::once(Vertex::MirStatement(MirOrigin::Borrowck(def_id), location)))
             },
         )
     }
 }
 
-pub(super) fn resolve_statement_edge<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_borrowck_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
@@ -702,31 +791,3200 @@ pub(super) fn resolve_statement_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "parent" => statement::parent(contexts, resolve_info, adapter),
+        "borrow" => borrowck::borrow(contexts, resolve_info, adapter),
+        "mir" => borrowck::mir(contexts, resolve_info, adapter),
+        "move" => borrowck::move_(contexts, resolve_info, adapter),
+        "region_constraint" => borrowck::region_constraint(contexts, resolve_info, adapter),
         _ => {
             unreachable!(
-                "attempted to resolve unexpected edge '{edge_name}' on type 'Statement'"
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Borrowck'"
             )
         }
     }
 }
 
-mod statement {
+mod borrowck {
+    use rustc_borrowck::consumers::BorrowIndex;
     use trustfall::provider::{
-        AsVertex, ContextIterator, ContextOutcomeIterator,
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
         ResolveEdgeInfo, VertexIterator,
     };
 
-    use crate::adapter::Adapter;
+    use crate::{adapter::{Adapter, util::mir_moves}, query_overrides::borrowck_facts};
 
-    use super::super::vertex::Vertex;
+    use super::super::vertex::{MirOrigin, Vertex};
 
-    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn borrow<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
         adapter: &'a Adapter,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
-        super::node::parent(contexts, _resolve_info, adapter)
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_borrowck()
+                    .expect("conversion failed, vertex was not a Borrowck");
+
+                let count = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let facts = borrowck_facts(ctxt, def_id).expect("borrowck facts should have been kept");
+                    facts.borrow_set.location_map.len()
+                });
+
+                Box::new((0..count).map(move |index| Vertex::Borrow(def_id, BorrowIndex::from_usize(index))))
+            },
+        )
+    }
+
+    pub(super) fn mir<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        _adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_borrowck()
+                    .expect("conversion failed, vertex was not a Borrowck");
+
+                Box::new(std::iter::once(Vertex::Mir(MirOrigin::Borrowck(def_id))))
+            },
+        )
+    }
+
+    pub(super) fn move_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_borrowck()
+                    .expect("conversion failed, vertex was not a Borrowck");
+
+                let count = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let facts = borrowck_facts(ctxt, def_id).expect("borrowck facts should have been kept");
+                    mir_moves(&facts.body).len()
+                });
+
+                Box::new((0..count).map(move |index| Vertex::Move(def_id, index)))
+            },
+        )
+    }
+
+    pub(super) fn region_constraint<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_borrowck()
+                    .expect("conversion failed, vertex was not a Borrowck");
+
+                let count = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let facts = borrowck_facts(ctxt, def_id).expect("borrowck facts should have been kept");
+                    facts.region_inference_context.outlives_constraints().count()
+                });
+
+                Box::new((0..count).map(move |index| Vertex::RegionConstraint(def_id, index)))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_const_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "body" => const_::body(contexts, resolve_info, adapter),
+        "declared_type" => const_::declared_type(contexts, resolve_info, adapter),
+        "evaluated_value" => const_::evaluated_value(contexts, resolve_info, adapter),
+        "doc_link" => const_::doc_link(contexts, resolve_info, adapter),
+        "parent" => const_::parent(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Const'"
+            )
+        }
+    }
+}
+
+mod const_ {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::{Adapter, util::eval_const_item};
+
+    use super::super::vertex::{TyOrigin, Vertex};
+
+    pub(super) fn body<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("expected vertex to be an Item");
+
+                let body_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.hir().body_owned_by(item_id.owner_id.def_id)
+                });
+
+                Box::new(std::iter::once(Vertex::Body(body_id)))
+            },
+        )
+    }
+
+    pub(super) fn declared_type<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        _adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("expected vertex to be an Item");
+
+                let origin = TyOrigin::TypeOf(item_id.owner_id.to_def_id());
+                Box::new(std::iter::once(Vertex::Ty(origin)))
+            },
+        )
+    }
+
+    pub(super) fn evaluated_value<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("expected vertex to be an Item");
+
+                let def_id = item_id.owner_id.to_def_id();
+                let evaluated = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    eval_const_item(ctxt, adapter, def_id).is_ok()
+                });
+
+                if evaluated {
+                    Box::new(std::iter::once(Vertex::ConstValue(def_id, vec![])))
+                } else {
+                    Box::new(std::iter::empty())
+                }
+            },
+        )
+    }
+
+    pub(super) fn doc_link<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::item::doc_link(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::item::parent(contexts, _resolve_info, adapter)
+    }
+}
+
+pub(super) fn resolve_const_value_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "field" => const_value::field(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'ConstValue'"
+            )
+        }
+    }
+}
+
+mod const_value {
+    use itertools::Itertools;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::{Adapter, util::{destructure_const, evaluated_const_field}};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn field<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (def_id, path) = vertex
+                    .as_const_value()
+                    .expect("conversion failed, vertex was not a ConstValue");
+
+                let field_count = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    evaluated_const_field(ctxt, adapter, *def_id, path)
+                        .and_then(|(value, ty)| destructure_const(ctxt, value, ty))
+                        .map_or(0, |destructured| destructured.fields.len())
+                });
+
+                let def_id = *def_id;
+                let fields = (0..field_count)
+                    .map(|index| {
+                        let mut path = path.clone();
+                        path.push(index);
+                        Vertex::ConstValue(def_id, path)
+                    })
+                    .collect_vec();
+                Box::new(fields.into_iter())
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_coroutine_layout_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "saved_local" => coroutine_layout::saved_local(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'CoroutineLayout'"
+            )
+        }
+    }
+}
+
+mod coroutine_layout {
+    use itertools::Itertools;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn saved_local<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_coroutine_layout()
+                    .expect("vertex was not variant 'CoroutineLayout'");
+
+                let locals = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt
+                        .coroutine_layout(def_id)
+                        .expect("coroutine should have a layout")
+                        .field_tys
+                        .indices()
+                        .map(|local| Vertex::CoroutineSavedLocal(def_id, local))
+                        .collect_vec()
+                });
+
+                Box::new(locals.into_iter())
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_coroutine_saved_local_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "type" => coroutine_saved_local::type_(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'CoroutineSavedLocal'"
+            )
+        }
+    }
+}
+
+mod coroutine_saved_local {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::{Adapter, vertex::TyOrigin};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        _adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (def_id, local) = vertex
+                    .as_coroutine_saved_local()
+                    .expect("vertex was not variant 'CoroutineSavedLocal'");
+
+                Box::new(std::iter::once(Vertex::Ty(TyOrigin::CoroutineSavedLocal(*def_id, *local))))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_crate_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "item" => crate_::item(contexts, resolve_info, adapter),
+        "fn" => crate_::fn_(contexts, resolve_info, adapter),
+        "expr" => crate_::expr(contexts, resolve_info, adapter),
+        "feature" => crate_::feature(contexts, resolve_info, adapter),
+        "dependency" => crate_::dependency(contexts, resolve_info, adapter),
+        "exported_item" => crate_::exported_item(contexts, resolve_info, adapter),
+        "lang_item" => crate_::lang_item(contexts, resolve_info, adapter),
+        "diagnostic_item" => crate_::diagnostic_item(contexts, resolve_info, adapter),
+        "unsafe_block" => crate_::unsafe_block(contexts, resolve_info, adapter),
+        "mono_item" => crate_::mono_item(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Crate'"
+            )
+        }
+    }
+}
+
+mod crate_ {
+    use itertools::Itertools;
+    use rustc_hir::intravisit::{Visitor, walk_expr};
+    use rustc_data_structures::fx::FxHashSet;
+    use rustc_hir::{def::{DefKind, Res}, ItemKind};
+    use rustc_middle::hir::{nested_filter::OnlyBodies, map::Map};
+    use rustc_span::def_id::LOCAL_CRATE;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::{Adapter, util::{crates, is_unsafe_block, module_children}};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn item<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let krate = *vertex
+                    .as_crate()
+                    .expect("conversion failed, vertex was not a Crate");
+                if krate != LOCAL_CRATE {
+                    // Only the local crate has HIR.
+                    return Box::new(std::iter::empty());
+                }
+                let items = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let hir = ctxt.hir();
+                    hir
+                        .items()
+                        .map(|id| Vertex::from_item(hir.item(id)))
+                        .collect_vec()
+                });
+                Box::new(items.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn fn_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let krate = *vertex
+                    .as_crate()
+                    .expect("conversion failed, vertex was not a Crate");
+                if krate != LOCAL_CRATE {
+                    // Only the local crate has HIR.
+                    return Box::new(std::iter::empty());
+                }
+                let fns = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let hir = ctxt.hir();
+                    hir
+                        .items()
+                        .flat_map(|id| {
+                            let item = hir.item(id);
+                            match item.kind {
+                                ItemKind::Fn(..) => vec![Vertex::from_item(item)],
+                                ItemKind::Impl(impl_) => impl_
+                                    .items
+                                    .iter()
+                                    .filter_map(|impl_item| Vertex::from_impl_item(hir.impl_item(impl_item.id)))
+                                    .collect_vec(),
+                                _ => vec![],
+                            }
+                        })
+                        .collect_vec()
+                });
+                Box::new(fns.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn feature<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let krate = *vertex
+                    .as_crate()
+                    .expect("conversion failed, vertex was not a Crate");
+                if krate != LOCAL_CRATE {
+                    // Dependencies were compiled with their own feature gates, which aren't recorded.
+                    return Box::new(std::iter::empty());
+                }
+                let features = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let features = ctxt.features();
+                    features
+                        .declared_lang_features
+                        .iter()
+                        .map(|&(name, ..)| Vertex::Feature(name))
+                        .chain(features.declared_lib_features.iter().map(|&(name, _)| Vertex::Feature(name)))
+                        .collect_vec()
+                });
+                Box::new(features.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn dependency<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let krate = *vertex
+                    .as_crate()
+                    .expect("conversion failed, vertex was not a Crate");
+                if krate != LOCAL_CRATE {
+                    // The crate store only knows about the dependencies of the whole build.
+                    return Box::new(std::iter::empty());
+                }
+                let dependencies = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    crates(ctxt).iter().map(|&cnum| Vertex::Crate(cnum)).collect_vec()
+                });
+                Box::new(dependencies.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn exported_item<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let krate = *vertex
+                    .as_crate()
+                    .expect("conversion failed, vertex was not a Crate");
+                let items = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let root = krate.as_def_id();
+                    let mut seen_modules = FxHashSet::from_iter([root]);
+                    let mut modules = vec![root];
+                    let mut items = Vec::new();
+                    while let Some(module) = modules.pop() {
+                        for child in module_children(ctxt, module) {
+                            let Res::Def(kind, def_id) = child.res else {
+                                continue;
+                            };
+                            if !child.vis.is_public() {
+                                continue;
+                            }
+                            // Glob and cyclic re-exports can lead back to modules that were already walked.
+                            if kind == DefKind::Mod && seen_modules.insert(def_id) {
+                                modules.push(def_id);
+                            }
+                            items.push(Vertex::Def(def_id));
+                        }
+                    }
+                    items
+                });
+                Box::new(items.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn lang_item<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let krate = *vertex
+                    .as_crate()
+                    .expect("conversion failed, vertex was not a Crate");
+                let items = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.lang_items()
+                        .iter()
+                        .filter(|(_, def_id)| def_id.krate == krate)
+                        .map(|(_, def_id)| Vertex::Def(def_id))
+                        .collect_vec()
+                });
+                Box::new(items.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn diagnostic_item<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let krate = *vertex
+                    .as_crate()
+                    .expect("conversion failed, vertex was not a Crate");
+                let items = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.diagnostic_items(krate)
+                        .name_to_id
+                        .values()
+                        .map(|def_id| Vertex::Def(*def_id))
+                        .collect_vec()
+                });
+                Box::new(items.into_iter())
+            },
+        )
+    }
+
+    #[derive(Clone)]
+    struct AllExprs<'a> {
+        map: Map<'a>,
+        pub exprs: Vec<Vertex>
+    }
+
+    impl<'a> Visitor<'a> for AllExprs<'a> {
+        type NestedFilter = OnlyBodies;
+
+        fn nested_visit_map(&mut self) -> Self::Map {
+            self.map
+        }
+
+        fn visit_expr(&mut self, ex: &'a rustc_hir::Expr<'a>) {
+            let vertex = Vertex::from_expr(ex);
+            self.exprs.push(vertex);
+            walk_expr(self, ex);
+        }
+    }
+
+    pub(super) fn expr<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let krate = *vertex
+                    .as_crate()
+                    .expect("conversion failed, vertex was not a Crate");
+                if krate != LOCAL_CRATE {
+                    // Only the local crate has HIR.
+                    return Box::new(std::iter::empty());
+                }
+                let exprs = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let hir = ctxt.hir();
+                    let mut all_exprs = AllExprs { map: hir, exprs: Vec::new() };
+                    hir.visit_all_item_likes_in_crate(&mut all_exprs);
+                    all_exprs.exprs
+                });
+                Box::new(exprs.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn unsafe_block<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let krate = *vertex
+                    .as_crate()
+                    .expect("conversion failed, vertex was not a Crate");
+                if krate != LOCAL_CRATE {
+                    // Only the local crate has HIR.
+                    return Box::new(std::iter::empty());
+                }
+                let blocks = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let hir = ctxt.hir();
+                    let mut all_exprs = AllExprs { map: hir, exprs: Vec::new() };
+                    hir.visit_all_item_likes_in_crate(&mut all_exprs);
+                    all_exprs.exprs
+                        .into_iter()
+                        .filter(|vertex| {
+                            matches!(vertex, Vertex::Block(hir_id) if is_unsafe_block(hir.expect_expr(*hir_id)))
+                        })
+                        .collect_vec()
+                });
+                Box::new(blocks.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn mono_item<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let krate = *vertex
+                    .as_crate()
+                    .expect("conversion failed, vertex was not a Crate");
+                if krate != LOCAL_CRATE {
+                    // Dependencies were codegened when they were compiled.
+                    return Box::new(std::iter::empty());
+                }
+                let count = adapter.queries.global_ctxt().unwrap().enter(|ctxt| adapter.mono_items(ctxt).len());
+                Box::new((0..count).map(Vertex::Instance))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_def_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "stability" => def::stability(contexts, resolve_info, adapter),
+        "const_stability" => def::const_stability(contexts, resolve_info, adapter),
+        "deprecation" => def::deprecation(contexts, resolve_info, adapter),
+        "attribute" => def::attribute(contexts, resolve_info, adapter),
+        "doc_link" => def::doc_link(contexts, resolve_info, adapter),
+        "parent" => def::parent(contexts, resolve_info, adapter),
+        "child" => def::child(contexts, resolve_info, adapter),
+        "item" => def::item(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Def'"
+            )
+        }
+    }
+}
+
+mod def {
+    use itertools::Itertools;
+    use rustc_hir::{def::DefKind, Node};
+    use rustc_span::def_id::DefId;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::{Adapter, util::{doc_links, module_children}};
+
+    use super::super::vertex::{AttrOrigin, Vertex};
+
+    pub(super) fn stability<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .as_def()
+                    .expect("vertex was not variant 'Def'");
+
+                let is_stable = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.lookup_stability(def_id).map(|stability| stability.level.is_stable())
+                });
+
+                match is_stable {
+                    Some(true) => Box::new(std::iter::once(Vertex::StableLevel(*def_id))),
+                    Some(false) => Box::new(std::iter::once(Vertex::UnstableLevel(*def_id))),
+                    None => Box::new(std::iter::empty()),
+                }
+            },
+        )
+    }
+
+    pub(super) fn const_stability<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .as_def()
+                    .expect("vertex was not variant 'Def'");
+
+                let is_stable = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.lookup_const_stability(def_id).map(|stability| stability.level.is_stable())
+                });
+
+                match is_stable {
+                    Some(true) => Box::new(std::iter::once(Vertex::ConstStableLevel(*def_id))),
+                    Some(false) => Box::new(std::iter::once(Vertex::ConstUnstableLevel(*def_id))),
+                    None => Box::new(std::iter::empty()),
+                }
+            },
+        )
+    }
+
+    pub(super) fn deprecation<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .as_def()
+                    .expect("vertex was not variant 'Def'");
+
+                let deprecation_exists = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.lookup_deprecation_entry(def_id).is_some()
+                });
+
+                if deprecation_exists {
+                    Box::new(std::iter::once(Vertex::Deprecation(*def_id)))
+                } else {
+                    Box::new(std::iter::empty())
+                }
+            },
+        )
+    }
+
+    pub(super) fn attribute<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_def()
+                    .expect("vertex was not variant 'Def'");
+
+                let count = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.get_attrs_unchecked(def_id).len()
+                });
+
+                Box::new((0..count).map(move |index| Vertex::Attribute(AttrOrigin::Def(def_id, index))))
+            },
+        )
+    }
+
+    pub(super) fn doc_link<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .as_def()
+                    .expect("vertex was not variant 'Def'");
+
+                doc_links_of(*def_id, adapter)
+            },
+        )
+    }
+
+    pub(super) fn doc_links_of<'a>(def_id: DefId, adapter: &Adapter) -> VertexIterator<'a, Vertex> {
+        let count = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+            doc_links(ctxt.get_attrs_unchecked(def_id)).len()
+        });
+
+        Box::new((0..count).map(move |index| Vertex::DocLink(def_id, index)))
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_def()
+                    .expect("vertex was not variant 'Def'");
+
+                let parent = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.opt_parent(def_id)
+                });
+
+                Box::new(parent.into_iter().map(Vertex::Def))
+            },
+        )
+    }
+
+    pub(super) fn child<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_def()
+                    .expect("vertex was not variant 'Def'");
+
+                let children = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    match ctxt.def_kind(def_id) {
+                        // Re-exports are children of the module they're in, but their parent is
+                        // the module they're defined in.
+                        DefKind::Mod | DefKind::Enum | DefKind::Trait => module_children(ctxt, def_id)
+                            .iter()
+                            .filter_map(|child| child.res.opt_def_id())
+                            .filter(|child| ctxt.opt_parent(*child) == Some(def_id))
+                            .collect_vec(),
+                        DefKind::Impl { .. } => ctxt.associated_item_def_ids(def_id).to_vec(),
+                        DefKind::Struct | DefKind::Union => ctxt
+                            .adt_def(def_id)
+                            .non_enum_variant()
+                            .fields
+                            .iter()
+                            .map(|field| field.did)
+                            .collect_vec(),
+                        DefKind::Variant => ctxt
+                            .adt_def(ctxt.parent(def_id))
+                            .variant_with_id(def_id)
+                            .fields
+                            .iter()
+                            .map(|field| field.did)
+                            .collect_vec(),
+                        _ => vec![],
+                    }
+                });
+
+                Box::new(children.into_iter().map(Vertex::Def))
+            },
+        )
+    }
+
+    pub(super) fn item<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_def()
+                    .expect("vertex was not variant 'Def'");
+
+                let item = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    match ctxt.hir().get_if_local(def_id) {
+                        Some(Node::Item(item)) => Some(Vertex::from_item(item)),
+                        Some(Node::ImplItem(impl_item)) => Vertex::from_impl_item(impl_item),
+                        _ => None,
+                    }
+                });
+
+                Box::new(item.into_iter())
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_deref_adjustment_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "overloaded" => deref_adjustment::overloaded(contexts, resolve_info, adapter),
+        "target" => deref_adjustment::target(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'DerefAdjustment'"
+            )
+        }
+    }
+}
+
+mod deref_adjustment {
+    use rustc_middle::ty::{self, adjustment::Adjust};
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn overloaded<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (hir_id, index) = vertex
+                    .adjustment()
+                    .expect("vertex was not an Adjustment");
+
+                let opt_def_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let typeck = ctxt.typeck(ctxt.hir().enclosing_body_owner(hir_id));
+                    let adjustments = &typeck.adjustments()[hir_id];
+                    let Adjust::Deref(Some(overloaded)) = adjustments[index].kind else {
+                        return None;
+                    };
+                    let source = match index.checked_sub(1) {
+                        Some(previous) => adjustments[previous].target,
+                        None => typeck.node_type(hir_id),
+                    };
+                    match overloaded.method_call(ctxt, source).kind() {
+                        ty::FnDef(def_id, _) => Some(*def_id),
+                        _ => None,
+                    }
+                });
+
+                if let Some(def_id) = opt_def_id {
+                    Box::new(std::iter::once(Vertex::Def(def_id)))
+                } else {
+                    Box::new(std::iter::empty())
+                }
+            },
+        )
+    }
+
+    pub(super) fn target<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::adjustment::target(contexts, _resolve_info, adapter)
+    }
+}
+
+pub(super) fn resolve_doc_link_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "target" => doc_link::target(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'DocLink'"
+            )
+        }
+    }
+}
+
+mod doc_link {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::{Adapter, util::{doc_links, resolve_doc_link}};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn target<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (def_id, index) = vertex
+                    .as_doc_link()
+                    .expect("conversion failed, vertex was not a DocLink");
+
+                let target = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let link = &doc_links(ctxt.get_attrs_unchecked(*def_id))[*index];
+                    resolve_doc_link(ctxt, *def_id, link)
+                });
+
+                if let Some(target) = target {
+                    Box::new(std::iter::once(Vertex::Def(target)))
+                } else {
+                    Box::new(std::iter::empty())
+                }
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_expr_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "adjusted_type" => expr::adjusted_type(contexts, resolve_info, adapter),
+        "adjustment" => expr::adjustment(contexts, resolve_info, adapter),
+        "parent" => expr::parent(contexts, resolve_info, adapter),
+        "type" => expr::type_(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Expr'"
+            )
+        }
+    }
+}
+
+mod expr {
+    use itertools::Itertools;
+    use rustc_middle::ty::{adjustment::Adjust, Instance, InstanceDef};
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::{Adapter, vertex::TyOrigin};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn adjusted_type<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        _adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .hir_id()
+                    .expect("vertex was not a Node");
+
+                Box::new(std::iter::once(Vertex::Ty(TyOrigin::Adjusted(hir_id))))
+            },
+        )
+    }
+
+    pub(super) fn adjustment<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .hir_id()
+                    .expect("vertex was not a Node");
+
+                let adjustments = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let hir = ctxt.hir();
+                    ctxt
+                        .typeck(hir.enclosing_body_owner(hir_id))
+                        .expr_adjustments(hir.expect_expr(hir_id))
+                        .iter()
+                        .enumerate()
+                        .map(|(index, adjustment)| match adjustment.kind {
+                            Adjust::NeverToAny => Vertex::NeverToAnyAdjustment(hir_id, index),
+                            Adjust::Deref(..) => Vertex::DerefAdjustment(hir_id, index),
+                            Adjust::Borrow(..) => Vertex::BorrowAdjustment(hir_id, index),
+                            Adjust::Pointer(..) => Vertex::PointerAdjustment(hir_id, index),
+                            Adjust::DynStar => Vertex::DynStarAdjustment(hir_id, index),
+                        })
+                        .collect_vec()
+                });
+
+                Box::new(adjustments.into_iter())
+            },
+        )
+    }
+
+    /// The method of the impl an overloaded operator expression calls, if the impl can be known
+    /// without knowing the generic parameters of the enclosing function.
+    pub(super) fn overloaded_impl_method<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .hir_id()
+                    .expect("vertex was not a Node");
+
+                let opt_def_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let hir = ctxt.hir();
+                    let owner = hir.enclosing_body_owner(hir_id);
+                    let typeck = ctxt.typeck(owner);
+                    if !typeck.is_method_call(hir.expect_expr(hir_id)) {
+                        return None;
+                    }
+                    let method = typeck.type_dependent_def_id(hir_id)?;
+                    // Same as for MIR calls, but without falling back to the trait method.
+                    match Instance::resolve(ctxt, ctxt.param_env(owner), method, typeck.node_args(hir_id)) {
+                        Ok(Some(Instance { def: InstanceDef::Item(def_id), .. })) => Some(def_id),
+                        _ => None,
+                    }
+                });
+
+                Box::new(opt_def_id.into_iter().map(Vertex::Def))
+            },
+        )
+    }
+
+    /// The trait method called by an overloaded operator expression.
+    pub(super) fn overloaded_method<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .hir_id()
+                    .expect("vertex was not a Node");
+
+                let opt_def_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let hir = ctxt.hir();
+                    let typeck = ctxt.typeck(hir.enclosing_body_owner(hir_id));
+                    if typeck.is_method_call(hir.expect_expr(hir_id)) {
+                        typeck.type_dependent_def_id(hir_id)
+                    } else {
+                        None
+                    }
+                });
+
+                if let Some(def_id) = opt_def_id {
+                    Box::new(std::iter::once(Vertex::Def(def_id)))
+                } else {
+                    Box::new(std::iter::empty())
+                }
+            },
+        )
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::node::parent(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        _adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .hir_id()
+                    .expect("conversion failed, vertex was not an Item");
+                
+                Box::new(std::iter::once(Vertex::Ty(TyOrigin::Node(hir_id))))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_fn_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "body" => fn_::body(contexts, resolve_info, adapter),
+        "borrowck" => fn_::borrowck(contexts, resolve_info, adapter),
+        "coroutine_layout" => fn_::coroutine_layout(contexts, resolve_info, adapter),
+        "doc_link" => fn_::doc_link(contexts, resolve_info, adapter),
+        "mir" => {
+            let phase = parameters
+                .get("phase")
+                .and_then(FieldValue::as_str)
+                .expect("mir should have a phase");
+            fn_::mir(contexts, phase, resolve_info, adapter)
+        }
+        "panic_site" => fn_::panic_site(contexts, resolve_info, adapter),
+        "parent" => fn_::parent(contexts, resolve_info, adapter),
+        "thir" => fn_::thir(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Fn'"
+            )
+        }
+    }
+}
+
+mod fn_ {
+    use rustc_span::def_id::LocalDefId;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::{Adapter, util::panic_sites};
+
+    use crate::query_overrides::{borrowck_facts, built_mir, with_thir};
+
+    use super::super::vertex::{MirOrigin, Vertex};
+
+    pub(super) fn body<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("expected vertex to be an Item");
+
+                let body_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.hir().body_owned_by(item_id.owner_id.def_id)
+                });
+
+                Box::new(std::iter::once(Vertex::FnBody(body_id)))
+            },
+        )
+    }
+
+    pub(super) fn borrowck<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("expected vertex to be an Item")
+                    .owner_id
+                    .def_id;
+
+                let is_available = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    borrowck_facts(ctxt, def_id).is_some()
+                });
+
+                if is_available {
+                    Box::new(std::iter::once(Vertex::Borrowck(def_id)))
+                } else {
+                    Box::new(std::iter::empty())
+                }
+            },
+        )
+    }
+
+    pub(super) fn coroutine_layout<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("expected vertex to be an Item");
+
+                let value_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let hir = ctxt.hir();
+                    hir.body(hir.body_owned_by(item_id.owner_id.def_id)).value.hir_id
+                });
+
+                super::async_block::coroutine_layout_of(value_id, adapter)
+            },
+        )
+    }
+
+    pub(super) fn doc_link<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::item::doc_link(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn mir<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        phase: &str,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        let origin: Option<fn(LocalDefId) -> MirOrigin> = match phase {
+            "built" => Some(MirOrigin::Built),
+            "optimized" => Some(MirOrigin::Optimized),
+            _ => None,
+        };
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("expected vertex to be an Item");
+                let Some(origin) = origin else {
+                    return Box::new(std::iter::empty());
+                };
+
+                let is_available = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    match origin(item_id.owner_id.def_id) {
+                        MirOrigin::Built(def_id) => built_mir(ctxt, def_id).is_some(),
+                        _ => true,
+                    }
+                });
+
+                if is_available {
+                    Box::new(std::iter::once(Vertex::Mir(origin(item_id.owner_id.def_id))))
+                } else {
+                    Box::new(std::iter::empty())
+                }
+            },
+        )
+    }
+
+    pub(super) fn panic_site<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("expected vertex to be an Item")
+                    .owner_id
+                    .def_id;
+
+                let sites = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    panic_sites(ctxt, def_id)
+                });
+
+                let origin = MirOrigin::Optimized(def_id);
+                Box::new(sites.into_iter().map(move |(block, kind)| Vertex::PanicSite(origin, block, kind)))
+            },
+        )
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::item::parent(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn thir<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("expected vertex to be an Item")
+                    .owner_id
+                    .def_id;
+
+                let is_available = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |_, _| ()).is_some()
+                });
+
+                if is_available {
+                    Box::new(std::iter::once(Vertex::Thir(def_id)))
+                } else {
+                    Box::new(std::iter::empty())
+                }
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_fn_body_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "parent" => fn_body::parent(contexts, resolve_info, adapter),
+        "value" => fn_body::value(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'FnBody'"
+            )
+        }
+    }
+}
+
+mod fn_body {
+    use trustfall::provider::{
+        AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::body::parent(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn value<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::body::value(contexts, _resolve_info, adapter)
+    }
+}
+
+pub(super) fn resolve_index_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "adjusted_type" => index::adjusted_type(contexts, resolve_info, adapter),
+        "adjustment" => index::adjustment(contexts, resolve_info, adapter),
+        "overloaded_impl_method" => index::overloaded_impl_method(contexts, resolve_info, adapter),
+        "overloaded_method" => index::overloaded_method(contexts, resolve_info, adapter),
+        "parent" => index::parent(contexts, resolve_info, adapter),
+        "type" => index::type_(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Index'"
+            )
+        }
+    }
+}
+
+mod index {
+    use trustfall::provider::{
+        AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn adjusted_type<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjusted_type(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn adjustment<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjustment(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn overloaded_impl_method<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::overloaded_impl_method(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn overloaded_method<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::overloaded_method(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::parent(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::type_(contexts, _resolve_info, adapter)
+    }
+}
+
+pub(super) fn resolve_instance_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "def" => instance::def(contexts, resolve_info, adapter),
+        "generic_arg" => instance::generic_arg(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Instance'"
+            )
+        }
+    }
+}
+
+mod instance {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::{Adapter, util::mono_item_arg_tys};
+
+    use super::super::vertex::{TyOrigin, Vertex};
+
+    pub(super) fn def<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let instance = *vertex
+                    .as_instance()
+                    .expect("conversion failed, vertex was not an Instance");
+
+                let def_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let (item, _, _) = adapter.mono_items(ctxt)[instance];
+                    item.def_id()
+                });
+
+                Box::new(std::iter::once(Vertex::Def(def_id)))
+            },
+        )
+    }
+
+    pub(super) fn generic_arg<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let instance = *vertex
+                    .as_instance()
+                    .expect("conversion failed, vertex was not an Instance");
+
+                let count = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let (item, _, _) = adapter.mono_items(ctxt)[instance];
+                    mono_item_arg_tys(item).len()
+                });
+
+                Box::new((0..count).map(move |index| Vertex::Ty(TyOrigin::InstanceArg(instance, index))))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_item_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "doc_link" => item::doc_link(contexts, resolve_info, adapter),
+        "parent" => item::parent(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Item'"
+            )
+        }
+    }
+}
+
+mod item {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn doc_link<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("expected vertex to be an Item");
+
+                super::def::doc_links_of(item_id.owner_id.to_def_id(), adapter)
+            },
+        )
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::node::parent(contexts, _resolve_info, adapter)
+    }
+}
+
+pub(super) fn resolve_lit_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "adjusted_type" => lit::adjusted_type(contexts, resolve_info, adapter),
+        "adjustment" => lit::adjustment(contexts, resolve_info, adapter),
+        "parent" => lit::parent(contexts, resolve_info, adapter),
+        "type" => lit::type_(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Lit'"
+            )
+        }
+    }
+}
+
+mod lit {
+    use trustfall::provider::{
+        AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn adjusted_type<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjusted_type(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn adjustment<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjustment(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::parent(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::type_(contexts, _resolve_info, adapter)
+    }
+}
+
+pub(super) fn resolve_local_statement_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "init" => local_statement::init(contexts, resolve_info, adapter),
+        "parent" => local_statement::parent(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'LocalStatement'"
+            )
+        }
+    }
+}
+
+mod local_statement {
+    use rustc_hir::{StmtKind, Local, intravisit::Map};
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn init<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex: &Vertex| {
+                let hir_id: rustc_hir::HirId = vertex
+                    .hir_id()
+                    .expect("conversion failed, vertex was not a Node");
+
+                let opt_init_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let hir = ctxt.hir();
+                    let stmt = hir
+                        .find(hir_id)
+                        .expect("LocalStatement couldn't be found")
+                        .expect_stmt();
+                    let StmtKind::Local(Local { init: Some(init_expr), .. }) = stmt.kind else {
+                        return None;
+                    };
+                    Some(init_expr.hir_id)
+                });
+
+                if let Some(init_id) = opt_init_id {
+                    Box::new(std::iter::once(Vertex::Expr(init_id)))
+                } else {
+                    Box::new(std::iter::empty())
+                }
+            },
+        )
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::statement::parent(contexts, _resolve_info, adapter)
+    }
+}
+
+pub(super) fn resolve_meta_item_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "meta_item" => meta_item::meta_item(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'MetaItem'"
+            )
+        }
+    }
+}
+
+mod meta_item {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+    use crate::adapter::util::nested_meta_item;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn meta_item<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (origin, path) = vertex
+                    .as_meta_item()
+                    .expect("conversion failed, vertex was not a MetaItem");
+                let origin = *origin;
+
+                let count = adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+                    nested_meta_item(origin.attr(ctxt), path)
+                        .and_then(|item| item.meta_item_list().map(|list| list.len()))
+                        .unwrap_or(0)
+                });
+                let path = path.clone();
+                Box::new((0..count).map(move |index| {
+                    let mut path = path.clone();
+                    path.push(index);
+                    Vertex::MetaItem(origin, path)
+                }))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_method_call_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "adjusted_type" => method_call::adjusted_type(contexts, resolve_info, adapter),
+        "adjustment" => method_call::adjustment(contexts, resolve_info, adapter),
+        "parent" => method_call::parent(contexts, resolve_info, adapter),
+        "type" => method_call::type_(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'MethodCall'"
+            )
+        }
+    }
+}
+
+mod method_call {
+    use trustfall::provider::{
+        AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn adjusted_type<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjusted_type(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn adjustment<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjustment(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::parent(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::type_(contexts, _resolve_info, adapter)
+    }
+}
+
+pub(super) fn resolve_mir_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "basic_block" => mir::basic_block(contexts, resolve_info, adapter),
+        "local" => mir::local(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Mir'"
+            )
+        }
+    }
+}
+
+mod mir {
+    use rustc_middle::mir::{BasicBlock, Local};
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn basic_block<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let origin = *vertex
+                    .as_mir()
+                    .expect("conversion failed, vertex was not a Mir");
+
+                let count = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    origin.body(ctxt).basic_blocks.len()
+                });
+
+                Box::new((0..count).map(move |index| Vertex::BasicBlock(origin, BasicBlock::from_usize(index))))
+            },
+        )
+    }
+
+    pub(super) fn local<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let origin = *vertex
+                    .as_mir()
+                    .expect("conversion failed, vertex was not a Mir");
+
+                let count = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    origin.body(ctxt).local_decls.len()
+                });
+
+                Box::new((0..count).map(move |index| Vertex::MirLocal(origin, Local::from_usize(index))))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_mir_local_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "type" => mir_local::type_(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'MirLocal'"
+            )
+        }
+    }
+}
+
+mod mir_local {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::{TyOrigin, Vertex};
+
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        _adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&origin, &local) = vertex
+                    .as_mir_local()
+                    .expect("conversion failed, vertex was not a MirLocal");
+
+                Box::new(std::iter::once(Vertex::Ty(TyOrigin::MirLocal(origin, local))))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_move_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "local" => move_::local(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Move'"
+            )
+        }
+    }
+}
+
+mod move_ {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::{adapter::{Adapter, util::mir_moves}, query_overrides::borrowck_facts};
+
+    use super::super::vertex::{MirOrigin, Vertex};
+
+    pub(super) fn local<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&def_id, &index) = vertex
+                    .as_move()
+                    .expect("conversion failed, vertex was not a Move");
+
+                let local = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let facts = borrowck_facts(ctxt, def_id).expect("borrowck facts should have been kept");
+                    mir_moves(&facts.body)[index].1.local
+                });
+
+                Box::new(std::iter::once(Vertex::MirLocal(MirOrigin::Borrowck(def_id), local)))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_node_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "attribute" => node::attribute(contexts, resolve_info, adapter),
+        "parent" => node::parent(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Node'"
+            )
+        }
+    }
+}
+
+mod node {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::{AttrOrigin, Vertex};
+
+    pub(super) fn attribute<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .hir_id()
+                    .expect("conversion failed, vertex was not a Node");
+                let count = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.hir().attrs(hir_id).len()
+                });
+
+                Box::new((0..count).map(move |index| Vertex::Attribute(AttrOrigin::Node(hir_id, index))))
+            },
+        )
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .hir_id()
+                    .expect("conversion failed, vertex was not a Node");
+                let opt_parent_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let hir = ctxt.hir();
+                    hir.opt_parent_id(hir_id)
+                });
+
+                if let Some(parent_id) = opt_parent_id {
+                    Box::new(std::iter::once(Vertex::Node(parent_id)))
+                } else {
+                    Box::new(std::iter::empty())
+                }
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_panic_site_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "callee" => panic_site::callee(contexts, resolve_info, adapter),
+        "terminator" => panic_site::terminator(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'PanicSite'"
+            )
+        }
+    }
+}
+
+mod panic_site {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::{Adapter, util::mir_callee};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn callee<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&origin, &block, _) = vertex
+                    .as_panic_site()
+                    .expect("conversion failed, vertex was not a PanicSite");
+
+                let callee = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    mir_callee(ctxt, origin.body(ctxt), block)
+                });
+
+                Box::new(callee.into_iter().map(Vertex::Def))
+            },
+        )
+    }
+
+    pub(super) fn terminator<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&origin, &block, _) = vertex
+                    .as_panic_site()
+                    .expect("conversion failed, vertex was not a PanicSite");
+
+                let terminator = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    Vertex::from_terminator(ctxt, origin, block)
+                });
+
+                Box::new(std::iter::once(terminator))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_statement_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "parent" => statement::parent(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Statement'"
+            )
+        }
+    }
+}
+
+mod statement {
+    use trustfall::provider::{
+        AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::node::parent(contexts, _resolve_info, adapter)
+    }
+}
+
+pub(super) fn resolve_static_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "body" => static_::body(contexts, resolve_info, adapter),
+        "declared_type" => static_::declared_type(contexts, resolve_info, adapter),
+        "evaluated_value" => static_::evaluated_value(contexts, resolve_info, adapter),
+        "doc_link" => static_::doc_link(contexts, resolve_info, adapter),
+        "parent" => static_::parent(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Static'"
+            )
+        }
+    }
+}
+
+mod static_ {
+    use trustfall::provider::{
+        AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn body<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::const_::body(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn declared_type<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::const_::declared_type(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn evaluated_value<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::const_::evaluated_value(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn doc_link<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::item::doc_link(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::item::parent(contexts, _resolve_info, adapter)
+    }
+}
+
+pub(super) fn resolve_terminator_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "callee" => terminator::callee(contexts, resolve_info, adapter),
+        "successor" => terminator::successor(contexts, resolve_info, adapter),
+        "target" => terminator::target(contexts, resolve_info, adapter),
+        "type" => terminator::type_(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Terminator'"
+            )
+        }
+    }
+}
+
+mod terminator {
+    use itertools::Itertools;
+    use rustc_middle::mir::TerminatorKind;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::{Adapter, util::mir_callee};
+
+    use super::super::vertex::{TyOrigin, Vertex};
+
+    pub(super) fn callee<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (origin, block) = vertex
+                    .terminator()
+                    .expect("conversion failed, vertex was not a Terminator");
+
+                let callee = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    mir_callee(ctxt, origin.body(ctxt), block)
+                });
+
+                Box::new(callee.into_iter().map(Vertex::Def))
+            },
+        )
+    }
+
+    pub(super) fn successor<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (origin, block) = vertex
+                    .terminator()
+                    .expect("conversion failed, vertex was not a Terminator");
+
+                let successors = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    origin.body(ctxt).basic_blocks[block].terminator().successors().collect_vec()
+                });
+
+                Box::new(successors.into_iter().map(move |successor| Vertex::BasicBlock(origin, successor)))
+            },
+        )
+    }
+
+    pub(super) fn target<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (origin, block) = vertex
+                    .terminator()
+                    .expect("conversion failed, vertex was not a Terminator");
+
+                let target = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    match origin.body(ctxt).basic_blocks[block].terminator().kind {
+                        TerminatorKind::Call { target, .. } => target,
+                        TerminatorKind::Drop { target, .. }
+                        | TerminatorKind::Assert { target, .. } => Some(target),
+                        _ => unreachable!("{block:?} doesn't end in a call, drop or assert"),
+                    }
+                });
+
+                Box::new(target.into_iter().map(move |target| Vertex::BasicBlock(origin, target)))
+            },
+        )
+    }
+
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        _adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&origin, &block) = vertex
+                    .as_drop_terminator()
+                    .expect("conversion failed, vertex was not a DropTerminator");
+
+                Box::new(std::iter::once(Vertex::Ty(TyOrigin::DroppedPlace(origin, block))))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_thir_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "body" => thir::body(contexts, resolve_info, adapter),
+        "expr" => thir::expr(contexts, resolve_info, adapter),
+        "param" => thir::param(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Thir'"
+            )
+        }
+    }
+}
+
+mod thir {
+    use itertools::Itertools;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::{adapter::{Adapter, util::thir_pat_index}, query_overrides::with_thir};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn body<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_thir()
+                    .expect("conversion failed, vertex was not a Thir");
+
+                let body = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, body| Vertex::from_thir_expr(thir, def_id, body))
+                        .expect("THIR should have been kept")
+                });
+
+                Box::new(std::iter::once(body))
+            },
+        )
+    }
+
+    pub(super) fn expr<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_thir()
+                    .expect("conversion failed, vertex was not a Thir");
+
+                let exprs = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| {
+                        thir.exprs
+                            .indices()
+                            .map(|expr| Vertex::from_thir_expr(thir, def_id, expr))
+                            .collect_vec()
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(exprs.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn param<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_thir()
+                    .expect("conversion failed, vertex was not a Thir");
+
+                let pats = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, body| {
+                        thir.params
+                            .iter()
+                            .filter_map(|param| param.pat.as_deref())
+                            .map(|pat| Vertex::ThirPat(def_id, thir_pat_index(thir, body, pat)))
+                            .collect_vec()
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(pats.into_iter())
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_thir_arm_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "body" => thir_arm::body(contexts, resolve_info, adapter),
+        "guard" => thir_arm::guard(contexts, resolve_info, adapter),
+        "pattern" => thir_arm::pattern(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'ThirArm'"
+            )
+        }
+    }
+}
+
+mod thir_arm {
+    use rustc_middle::thir::Guard;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::{adapter::{Adapter, util::thir_pat_index}, query_overrides::with_thir};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn body<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&def_id, &arm) = vertex
+                    .as_thir_arm()
+                    .expect("conversion failed, vertex was not a ThirArm");
+
+                let body = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| Vertex::from_thir_expr(thir, def_id, thir[arm].body))
+                        .expect("THIR should have been kept")
+                });
+
+                Box::new(std::iter::once(body))
+            },
+        )
+    }
+
+    pub(super) fn guard<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&def_id, &arm) = vertex
+                    .as_thir_arm()
+                    .expect("conversion failed, vertex was not a ThirArm");
+
+                let guard = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| {
+                        thir[arm].guard.as_ref().map(|guard| match guard {
+                            Guard::If(expr) | Guard::IfLet(_, expr) => Vertex::from_thir_expr(thir, def_id, *expr),
+                        })
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(guard.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn pattern<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&def_id, &arm) = vertex
+                    .as_thir_arm()
+                    .expect("conversion failed, vertex was not a ThirArm");
+
+                let index = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, body| thir_pat_index(thir, body, &thir[arm].pattern))
+                        .expect("THIR should have been kept")
+                });
+
+                Box::new(std::iter::once(Vertex::ThirPat(def_id, index)))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_thir_expr_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "arg" => thir_expr::arg(contexts, resolve_info, adapter),
+        "arm" => thir_expr::arm(contexts, resolve_info, adapter),
+        "callee" => thir_expr::callee(contexts, resolve_info, adapter),
+        "descendant" => thir_expr::descendant(contexts, resolve_info, adapter),
+        "expr" => thir_expr::expr(contexts, resolve_info, adapter),
+        "receiver" => thir_expr::receiver(contexts, resolve_info, adapter),
+        "scrutinee" => thir_expr::scrutinee(contexts, resolve_info, adapter),
+        "statement" => thir_expr::statement(contexts, resolve_info, adapter),
+        "type" => thir_expr::type_(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'ThirExpr'"
+            )
+        }
+    }
+}
+
+mod thir_expr {
+    use itertools::Itertools;
+    use rustc_middle::{thir::ExprKind, ty::{self, Instance}};
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::{adapter::{Adapter, util::thir_descendants}, query_overrides::with_thir};
+
+    use super::super::vertex::{TyOrigin, Vertex};
+
+    pub(super) fn arg<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (def_id, expr) = vertex
+                    .thir_expr()
+                    .expect("conversion failed, vertex was not a ThirExpr");
+
+                let args = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| {
+                        match &thir[expr].kind {
+                            ExprKind::Call { args, .. } => args.to_vec(),
+                            ExprKind::Borrow { arg, .. } => vec![*arg],
+                            _ => unreachable!("{expr:?} isn't a call or borrow"),
+                        }
+                        .into_iter()
+                        .map(|arg| Vertex::from_thir_expr(thir, def_id, arg))
+                        .collect_vec()
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(args.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn arm<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (def_id, expr) = vertex
+                    .thir_expr()
+                    .expect("conversion failed, vertex was not a ThirExpr");
+
+                let arms = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| {
+                        let ExprKind::Match { arms, .. } = &thir[expr].kind else {
+                            unreachable!("{expr:?} isn't a match");
+                        };
+                        arms.to_vec()
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(arms.into_iter().map(move |arm| Vertex::ThirArm(def_id, arm)))
+            },
+        )
+    }
+
+    pub(super) fn callee<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (def_id, expr) = vertex
+                    .thir_expr()
+                    .expect("conversion failed, vertex was not a ThirExpr");
+
+                let callee = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| {
+                        let ExprKind::Call { ty, .. } = thir[expr].kind else {
+                            unreachable!("{expr:?} isn't a call");
+                        };
+                        let ty::FnDef(callee, args) = *ty.kind() else {
+                            return None;
+                        };
+                        // Same as for MIR calls, trait methods resolve to the method of the impl if it's known.
+                        match Instance::resolve(ctxt, ctxt.param_env(def_id), callee, args) {
+                            Ok(Some(instance)) => Some(instance.def_id()),
+                            _ => Some(callee),
+                        }
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(callee.into_iter().map(Vertex::Def))
+            },
+        )
+    }
+
+    pub(super) fn receiver<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (def_id, expr) = vertex
+                    .thir_expr()
+                    .expect("conversion failed, vertex was not a ThirExpr");
+
+                let receiver = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| {
+                        let ExprKind::Call { ty, ref args, .. } = thir[expr].kind else {
+                            unreachable!("{expr:?} isn't a call");
+                        };
+                        let ty::FnDef(callee, _) = *ty.kind() else {
+                            return None;
+                        };
+                        if !ctxt.opt_associated_item(callee).is_some_and(|item| item.fn_has_self_parameter) {
+                            return None;
+                        }
+                        // Autoref and autoderef wrap the receiver in borrows and derefs.
+                        let mut receiver = *args.first()?;
+                        loop {
+                            match thir[receiver].kind {
+                                ExprKind::Scope { value: inner, .. }
+                                | ExprKind::Borrow { arg: inner, .. }
+                                | ExprKind::Deref { arg: inner } => receiver = inner,
+                                _ => break Some(Vertex::from_thir_expr(thir, def_id, receiver)),
+                            }
+                        }
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(receiver.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn descendant<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (def_id, expr) = vertex
+                    .thir_expr()
+                    .expect("conversion failed, vertex was not a ThirExpr");
+
+                let descendants = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| {
+                        thir_descendants(thir, expr)
+                            .into_iter()
+                            .map(|descendant| Vertex::from_thir_expr(thir, def_id, descendant))
+                            .collect_vec()
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(descendants.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn expr<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (def_id, expr) = vertex
+                    .thir_expr()
+                    .expect("conversion failed, vertex was not a ThirExpr");
+
+                let tail = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| {
+                        let ExprKind::Block { block } = thir[expr].kind else {
+                            unreachable!("{expr:?} isn't a block");
+                        };
+                        thir[block].expr.map(|tail| Vertex::from_thir_expr(thir, def_id, tail))
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(tail.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn scrutinee<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (def_id, expr) = vertex
+                    .thir_expr()
+                    .expect("conversion failed, vertex was not a ThirExpr");
+
+                let scrutinee = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| {
+                        let ExprKind::Match { scrutinee, .. } = thir[expr].kind else {
+                            unreachable!("{expr:?} isn't a match");
+                        };
+                        Vertex::from_thir_expr(thir, def_id, scrutinee)
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(std::iter::once(scrutinee))
+            },
+        )
+    }
+
+    pub(super) fn statement<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (def_id, expr) = vertex
+                    .thir_expr()
+                    .expect("conversion failed, vertex was not a ThirExpr");
+
+                let statements = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| {
+                        let ExprKind::Block { block } = thir[expr].kind else {
+                            unreachable!("{expr:?} isn't a block");
+                        };
+                        thir[block].stmts.to_vec()
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(statements.into_iter().map(move |stmt| Vertex::ThirStmt(def_id, stmt)))
+            },
+        )
+    }
+
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        _adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (def_id, expr) = vertex
+                    .thir_expr()
+                    .expect("conversion failed, vertex was not a ThirExpr");
+
+                Box::new(std::iter::once(Vertex::Ty(TyOrigin::ThirExpr(def_id, expr))))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_thir_pat_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "subpattern" => thir_pat::subpattern(contexts, resolve_info, adapter),
+        "type" => thir_pat::type_(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'ThirPat'"
+            )
+        }
+    }
+}
+
+mod thir_pat {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::{adapter::{Adapter, util::thir_subpatterns}, query_overrides::with_thir};
+
+    use super::super::vertex::{TyOrigin, Vertex};
+
+    pub(super) fn subpattern<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&def_id, &index) = vertex
+                    .as_thir_pat()
+                    .expect("conversion failed, vertex was not a ThirPat");
+
+                let subpatterns = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, body| thir_subpatterns(thir, body, index))
+                        .expect("THIR should have been kept")
+                });
+
+                Box::new(subpatterns.into_iter().map(move |index| Vertex::ThirPat(def_id, index)))
+            },
+        )
+    }
+
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        _adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&def_id, &index) = vertex
+                    .as_thir_pat()
+                    .expect("conversion failed, vertex was not a ThirPat");
+
+                Box::new(std::iter::once(Vertex::Ty(TyOrigin::ThirPat(def_id, index))))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_thir_stmt_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "expr" => thir_stmt::expr(contexts, resolve_info, adapter),
+        "pattern" => thir_stmt::pattern(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'ThirStmt'"
+            )
+        }
+    }
+}
+
+mod thir_stmt {
+    use rustc_middle::thir::StmtKind;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::{adapter::{Adapter, util::thir_pat_index}, query_overrides::with_thir};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn expr<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&def_id, &stmt) = vertex
+                    .as_thir_stmt()
+                    .expect("conversion failed, vertex was not a ThirStmt");
+
+                let expr = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| {
+                        let expr = match thir[stmt].kind {
+                            StmtKind::Expr { expr, .. } => Some(expr),
+                            StmtKind::Let { initializer, .. } => initializer,
+                        };
+                        expr.map(|expr| Vertex::from_thir_expr(thir, def_id, expr))
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(expr.into_iter())
+            },
+        )
+    }
+
+    pub(super) fn pattern<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&def_id, &stmt) = vertex
+                    .as_thir_stmt()
+                    .expect("conversion failed, vertex was not a ThirStmt");
+
+                let index = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, body| match &thir[stmt].kind {
+                        StmtKind::Let { pattern, .. } => Some(thir_pat_index(thir, body, pattern)),
+                        StmtKind::Expr { .. } => None,
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(index.into_iter().map(move |index| Vertex::ThirPat(def_id, index)))
+            },
+        )
     }
 }
 
@@ -738,7 +3996,9 @@ pub(super) fn resolve_ty_edge<'a, V: AsVertex<Vertex> + 'a>(
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
+        "adt" => ty::adt(contexts, resolve_info, adapter),
         "def" => ty::def(contexts, resolve_info, adapter),
+        "drop_impl" => ty::drop_impl(contexts, resolve_info, adapter),
         _ => {
             unreachable!(
                 "attempted to resolve unexpected edge '{edge_name}' on type 'Ty'"
@@ -753,10 +4013,31 @@ mod ty {
         ResolveEdgeInfo, VertexIterator, resolve_neighbors_with,
     };
 
-    use crate::adapter::Adapter;
+    use crate::adapter::{Adapter, vertex::TyOrigin};
 
     use super::super::vertex::Vertex;
 
+    pub(super) fn adt<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let Vertex::Ty(origin) = vertex else {
+                    unimplemented!("vertex is not Ty: {vertex:#?}")
+                };
+
+                let opt_def_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    origin.ty(ctxt, adapter).ty_adt_def().map(|adt| adt.did())
+                });
+
+                Box::new(opt_def_id.into_iter().map(Vertex::Def))
+            },
+        )
+    }
+
     pub(super) fn def<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
@@ -765,14 +4046,17 @@ mod ty {
         resolve_neighbors_with(
             contexts,
             move |vertex| {
-                let Vertex::Ty(hir_id) = vertex else {
+                let Vertex::Ty(origin) = vertex else {
                     unimplemented!("vertex is not Ty: {vertex:#?}")
                 };
 
                 let opt_def_id = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let TyOrigin::Node(hir_id) = *origin else {
+                        return None;
+                    };
                     let hir = ctxt.hir();
-                    let enclosing_body_def = hir.enclosing_body_owner(*hir_id);
-                    ctxt.typeck(enclosing_body_def).type_dependent_def_id(*hir_id)
+                    let enclosing_body_def = hir.enclosing_body_owner(hir_id);
+                    ctxt.typeck(enclosing_body_def).type_dependent_def_id(hir_id)
                 });
 
                 if let Some(def_id) = opt_def_id {
@@ -783,4 +4067,191 @@ mod ty {
             },
         )
     }
+
+    pub(super) fn drop_impl<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let Vertex::Ty(origin) = vertex else {
+                    unimplemented!("vertex is not Ty: {vertex:#?}")
+                };
+
+                let opt_impl = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let adt = origin.ty(ctxt, adapter).ty_adt_def()?;
+                    let destructor = ctxt.adt_destructor(adt.did())?;
+                    // The destructor is the `drop` method, not the impl it's in.
+                    Some(ctxt.parent(destructor.did))
+                });
+
+                Box::new(opt_impl.into_iter().map(Vertex::Def))
+            },
+        )
+    }
+}
+
+pub(super) fn resolve_unary_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "adjusted_type" => unary::adjusted_type(contexts, resolve_info, adapter),
+        "adjustment" => unary::adjustment(contexts, resolve_info, adapter),
+        "overloaded_impl_method" => unary::overloaded_impl_method(contexts, resolve_info, adapter),
+        "overloaded_method" => unary::overloaded_method(contexts, resolve_info, adapter),
+        "parent" => unary::parent(contexts, resolve_info, adapter),
+        "type" => unary::type_(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'Unary'"
+            )
+        }
+    }
+}
+
+mod unary {
+    use trustfall::provider::{
+        AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::adapter::Adapter;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn adjusted_type<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjusted_type(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn adjustment<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::adjustment(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn overloaded_impl_method<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::overloaded_impl_method(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn overloaded_method<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::overloaded_method(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn parent<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::parent(contexts, _resolve_info, adapter)
+    }
+
+    pub(super) fn type_<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        super::expr::type_(contexts, _resolve_info, adapter)
+    }
+}
+
+pub(super) fn resolve_unsafe_operation_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "callee" => unsafe_operation::callee(contexts, resolve_info, adapter),
+        "expr" => unsafe_operation::expr(contexts, resolve_info, adapter),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'UnsafeOperation'"
+            )
+        }
+    }
+}
+
+mod unsafe_operation {
+    use rustc_middle::{thir::ExprKind, ty};
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveEdgeInfo, VertexIterator,
+    };
+
+    use crate::{adapter::Adapter, query_overrides::with_thir};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn callee<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&def_id, &expr) = vertex
+                    .as_unsafe_operation()
+                    .expect("conversion failed, vertex was not an UnsafeOperation");
+
+                let callee = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| {
+                        let ExprKind::Call { fun, .. } = thir[expr].kind else {
+                            return None;
+                        };
+                        match *thir[fun].ty.kind() {
+                            ty::FnDef(callee, _) => Some(callee),
+                            _ => None,
+                        }
+                    })
+                    .expect("THIR should have been kept")
+                });
+
+                Box::new(callee.into_iter().map(Vertex::Def))
+            },
+        )
+    }
+
+    pub(super) fn expr<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+        adapter: &'a Adapter,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(
+            contexts,
+            move |vertex| {
+                let (&def_id, &expr) = vertex
+                    .as_unsafe_operation()
+                    .expect("conversion failed, vertex was not an UnsafeOperation");
+
+                let expr = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| Vertex::from_thir_expr(thir, def_id, expr))
+                        .expect("THIR should have been kept")
+                });
+
+                Box::new(std::iter::once(expr))
+            },
+        )
+    }
 }
@@ -1,7 +1,214 @@
+use itertools::Itertools;
+use rustc_hir::{
+    def::DefKind, intravisit::{self, Visitor}, Expr, ForeignItem, ImplItem, Item, ItemId, ItemKind,
+    LangItem, OwnerId, Stmt, TraitItem,
+};
+use rustc_middle::hir::nested_filter::All;
+use rustc_span::{def_id::LOCAL_CRATE, BytePos, FileName, Span, Symbol};
 use trustfall::provider::{ResolveInfo, VertexIterator};
 
-use super::vertex::Vertex;
+use super::{util::def_path_res, vertex::Vertex, Adapter};
 
 pub(super) fn crate_<'a>(_resolve_info: &ResolveInfo) -> VertexIterator<'a, Vertex> {
-    Box::new(std::iter::once(Vertex::Crate(())))
+    Box::new(std::iter::once(Vertex::Crate(LOCAL_CRATE)))
+}
+
+pub(super) fn def_by_path<'a>(
+    path: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> VertexIterator<'a, Vertex> {
+    let def_ids = adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+        def_path_res(ctxt, path)
+    });
+
+    Box::new(def_ids.into_iter().map(Vertex::Def))
+}
+
+pub(super) fn fn_by_path<'a>(
+    path: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> VertexIterator<'a, Vertex> {
+    let fns = adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+        def_path_res(ctxt, path)
+            .into_iter()
+            .filter(|def_id| match ctxt.def_kind(*def_id) {
+                DefKind::Fn => true,
+                DefKind::AssocFn => ctxt.impl_of_method(*def_id).is_some(),
+                _ => false,
+            })
+            .filter_map(|def_id| def_id.as_local())
+            .map(|def_id| Vertex::Fn(ItemId { owner_id: OwnerId { def_id } }))
+            .collect_vec()
+    });
+
+    Box::new(fns.into_iter())
+}
+
+pub(super) fn item_by_name<'a>(
+    name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> VertexIterator<'a, Vertex> {
+    let name = Symbol::intern(name);
+    let items = adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+        let hir = ctxt.hir();
+        hir
+            .items()
+            .map(|id| hir.item(id))
+            .filter(|item| item.ident.name == name)
+            .map(Vertex::from_item)
+            .collect_vec()
+    });
+
+    Box::new(items.into_iter())
+}
+
+pub(super) fn lang_item<'a>(
+    name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> VertexIterator<'a, Vertex> {
+    let Some(item) = LangItem::from_name(Symbol::intern(name)) else {
+        return Box::new(std::iter::empty());
+    };
+    let def_id = adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+        ctxt.lang_items().get(item)
+    });
+
+    Box::new(def_id.into_iter().map(Vertex::Def))
+}
+
+pub(super) fn diagnostic_item<'a>(
+    name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> VertexIterator<'a, Vertex> {
+    let def_id = adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+        ctxt.get_diagnostic_item(Symbol::intern(name))
+    });
+
+    Box::new(def_id.into_iter().map(Vertex::Def))
+}
+
+/// The items, functions of impls, statements and expressions whose source covers the
+/// character at 1-based `line` and `col` in `file`, innermost first. Nodes coming from macro expansions are
+/// left out, since their spans don't point at what was written at that position.
+pub(super) fn node_at<'a>(
+    file: &str,
+    line: usize,
+    col: usize,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> VertexIterator<'a, Vertex> {
+    struct NodesAt<'tcx> {
+        ctxt: rustc_middle::ty::TyCtxt<'tcx>,
+        pos: BytePos,
+        nodes: Vec<Vertex>,
+    }
+
+    impl<'tcx> NodesAt<'tcx> {
+        /// Whether `span` was written at `pos`, so that its node is one of the results.
+        fn covers(&self, span: Span) -> bool {
+            !span.from_expansion() && self.contains(span)
+        }
+
+        /// Whether the source `span` was expanded from surrounds `pos`. Nodes that don't
+        /// can't have children at `pos` either, so they aren't walked.
+        fn contains(&self, span: Span) -> bool {
+            let span = span.source_callsite();
+            span.lo() <= self.pos && self.pos < span.hi()
+        }
+    }
+
+    impl<'tcx> Visitor<'tcx> for NodesAt<'tcx> {
+        type NestedFilter = All;
+
+        fn nested_visit_map(&mut self) -> Self::Map {
+            self.ctxt.hir()
+        }
+
+        fn visit_item(&mut self, item: &'tcx Item<'tcx>) {
+            // Out-of-line modules are declared in another file than their items.
+            let span = match item.kind {
+                ItemKind::Mod(module) if !self.contains(item.span) => module.spans.inner_span,
+                _ => item.span,
+            };
+            if !self.contains(span) {
+                return;
+            }
+            if self.covers(span) {
+                self.nodes.push(Vertex::from_item(item));
+            }
+            intravisit::walk_item(self, item);
+        }
+
+        fn visit_impl_item(&mut self, impl_item: &'tcx ImplItem<'tcx>) {
+            if !self.contains(impl_item.span) {
+                return;
+            }
+            if self.covers(impl_item.span) {
+                self.nodes.extend(Vertex::from_impl_item(impl_item));
+            }
+            intravisit::walk_impl_item(self, impl_item);
+        }
+
+        fn visit_trait_item(&mut self, trait_item: &'tcx TraitItem<'tcx>) {
+            if self.contains(trait_item.span) {
+                intravisit::walk_trait_item(self, trait_item);
+            }
+        }
+
+        fn visit_foreign_item(&mut self, foreign_item: &'tcx ForeignItem<'tcx>) {
+            if self.contains(foreign_item.span) {
+                intravisit::walk_foreign_item(self, foreign_item);
+            }
+        }
+
+        fn visit_stmt(&mut self, stmt: &'tcx Stmt<'tcx>) {
+            if !self.contains(stmt.span) {
+                return;
+            }
+            if self.covers(stmt.span) {
+                self.nodes.push(Vertex::from_hir_node(self.ctxt, stmt.hir_id));
+            }
+            intravisit::walk_stmt(self, stmt);
+        }
+
+        fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+            if !self.contains(expr.span) {
+                return;
+            }
+            if self.covers(expr.span) {
+                self.nodes.push(Vertex::from_expr(expr));
+            }
+            intravisit::walk_expr(self, expr);
+        }
+    }
+
+    let (Some(line_index), Some(col_index)) = (line.checked_sub(1), col.checked_sub(1)) else {
+        return Box::new(std::iter::empty());
+    };
+    let nodes = adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+        let source_file = ctxt.sess.source_map().files().iter().find(|source_file| {
+            match &source_file.name {
+                FileName::Custom(name) if name == file => true,
+                name => name.prefer_local().to_string() == file,
+            }
+        })?.clone();
+        let offset = source_file
+            .get_line(line_index)?
+            .char_indices()
+            .nth(col_index)?
+            .0;
+        let pos = source_file.line_bounds(line_index).start + BytePos(offset as u32);
+
+        let mut visitor = NodesAt { ctxt, pos, nodes: vec![] };
+        ctxt.hir().walk_toplevel_module(&mut visitor);
+        visitor.nodes.reverse();
+        Some(visitor.nodes)
+    });
+
+    Box::new(nodes.into_iter().flatten())
 }
@@ -9,4 +9,4 @@ mod tests;
 mod util;
 
 pub use adapter_impl::Adapter;
-pub use vertex::Vertex;
+pub use vertex::{AttrOrigin, MirOrigin, PanicKind, TyOrigin, Vertex};
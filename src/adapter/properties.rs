@@ -1,609 +1,2971 @@
-use rustc_attr::StabilityLevel;
+use rustc_ast::{AttrArgs, AttrArgsEq, AttrKind, AttrStyle, LitFloatType, LitIntType, LitKind, NestedMetaItem};
+use rustc_ast_pretty::pprust;
+use rustc_borrowck::{borrow_set::{BorrowData, TwoPhaseActivation}, consumers::OutlivesConstraint};
+use rustc_attr::{DeprecatedSince, Deprecation, StabilityLevel, StableSince};
+use rustc_hir::{ExprKind, ItemKind, Closure, ClosureKind, CoroutineKind, CoroutineSource, Unsafety};
+use rustc_session::cstore::CrateDepKind;
+use rustc_span::{def_id::{CrateNum, LocalDefId, LOCAL_CRATE}, sym, Span, Symbol};
+use rustc_middle::{mir::{self, mono::{MonoItem, MonoItemData}}, thir, ty::{self, TyCtxt, adjustment::{Adjust, AutoBorrow, AutoBorrowMutability, PointerCoercion}}};
 use trustfall::{FieldValue, provider::{AsVertex, ContextIterator, ContextOutcomeIterator, ResolveInfo, resolve_property_with}};
 
-use super::{vertex::Vertex, Adapter};
+use super::{vertex::Vertex, util::{
+    const_scalar_to_field_value, declared_feature, destructure_const, doc_links, evaluated_const_field, is_unsafe_block,
+    may_panic, mir_drops, mir_moves, nested_meta_item, resolve_doc_link, thir_pats, unsafe_operation_kind, variant_name,
+}, Adapter};
+use crate::{query_overrides::{borrowck_facts, with_thir}, util::stable_since};
 
-pub(super) fn resolve_def_property<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_assign_op_property<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     property_name: &str,
     _resolve_info: &ResolveInfo,
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
-        "ident" => resolve_property_with(
+        "is_overloaded" => resolve_property_with(
             contexts,
             move |vertex| {
-                if let Some(def_id) = vertex.def_id() {
-                    adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                        if let Some(ident) = ctxt.opt_item_ident(def_id) {
-                            FieldValue::String(ident.as_str().into())
-                        } else {
-                            FieldValue::NULL
-                        }
-                    })
-                } else {
-                    unimplemented!("{vertex:?} is not a Def")
-                }
+                let hir_id = vertex
+                    .as_assign_op()
+                    .expect("vertex is not variant 'AssignOp'");
+                shared::resolve_is_overloaded(*hir_id, adapter)
+            },
+        ),
+        "operator" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .as_assign_op()
+                    .expect("vertex is not variant 'AssignOp'");
+
+                let operator = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let expr = ctxt.hir().expect_expr(*hir_id);
+                    let ExprKind::AssignOp(op, ..) = expr.kind else {
+                        unimplemented!("expr was not of type AssignOp: {expr:#?}")
+                    };
+                    op.node.as_str()
+                });
+
+                FieldValue::String(operator.into())
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'AssignOp'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_attribute_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "is_doc" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let origin = vertex
+                    .as_attribute()
+                    .expect("vertex is not variant 'Attribute'");
+
+                let is_doc = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let attr = origin.attr(ctxt);
+                    attr.is_doc_comment() || attr.has_name(sym::doc)
+                });
+
+                FieldValue::Boolean(is_doc)
             },
         ),
         "path" => resolve_property_with(
             contexts,
             move |vertex| {
-                if let Some(def_id) = vertex.def_id() {
-                    adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                        FieldValue::String(ctxt.def_path_str(def_id).into())
-                    })
-                } else {
-                    unimplemented!("{vertex:?} is not a Def")
-                }
+                let origin = vertex
+                    .as_attribute()
+                    .expect("vertex is not variant 'Attribute'");
+
+                let path = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    match &origin.attr(ctxt).kind {
+                        AttrKind::Normal(normal) => pprust::path_to_string(&normal.item.path),
+                        AttrKind::DocComment(..) => sym::doc.to_string(),
+                    }
+                });
+
+                FieldValue::String(path.into())
+            },
+        ),
+        "style" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let origin = vertex
+                    .as_attribute()
+                    .expect("vertex is not variant 'Attribute'");
+
+                let style = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    match origin.attr(ctxt).style {
+                        AttrStyle::Inner => "inner",
+                        AttrStyle::Outer => "outer",
+                    }
+                });
+
+                FieldValue::String(style.into())
+            },
+        ),
+        "tokens" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let origin = vertex
+                    .as_attribute()
+                    .expect("vertex is not variant 'Attribute'");
+
+                let tokens = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let AttrKind::Normal(normal) = &origin.attr(ctxt).kind else {
+                        return None;
+                    };
+                    let tokens = match &normal.item.args {
+                        AttrArgs::Empty => String::new(),
+                        AttrArgs::Delimited(args) => pprust::tts_to_string(&args.tokens),
+                        AttrArgs::Eq(_, AttrArgsEq::Ast(expr)) => pprust::expr_to_string(expr),
+                        AttrArgs::Eq(_, AttrArgsEq::Hir(lit)) => lit.as_token_lit().to_string(),
+                    };
+                    Some(tokens)
+                });
+
+                tokens.map_or(FieldValue::Null, |tokens| FieldValue::String(tokens.into()))
+            },
+        ),
+        "value_str" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let origin = vertex
+                    .as_attribute()
+                    .expect("vertex is not variant 'Attribute'");
+
+                let value = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let attr = origin.attr(ctxt);
+                    attr.value_str().or_else(|| attr.doc_str()).map(|value| value.to_string())
+                });
+
+                value.map_or(FieldValue::Null, |value| FieldValue::String(value.into()))
             },
         ),
         _ => {
             unreachable!(
-                "attempted to read unexpected property '{property_name}' on type 'Def'"
+                "attempted to read unexpected property '{property_name}' on type 'Attribute'"
             )
         }
     }
 }
 
-pub(super) fn resolve_fn_property<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_async_block_property<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     property_name: &str,
     _resolve_info: &ResolveInfo,
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
-        "ident" => resolve_property_with(
+        "source" => resolve_property_with(
             contexts,
             move |vertex| {
-                if let Some(item_id) = vertex.item_id() {
-                    shared::resolve_ident(item_id, adapter)
-                } else {
-                    unimplemented!("{vertex:?} is not an Item")
-                }
+                let hir_id = vertex
+                    .as_async_block()
+                    .expect("vertex is not variant 'AsyncBlock'");
+
+                let source = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let hir = ctxt.hir();
+                    let expr = hir.expect_expr(*hir_id);
+                    let ExprKind::Closure(Closure { kind: ClosureKind::Coroutine(CoroutineKind::Desugared(_, source)), .. }) = expr.kind else {
+                        unimplemented!("expr was not a desugared coroutine: {expr:#?}")
+                    };
+                    source
+                });
+
+                FieldValue::String(match source {
+                    CoroutineSource::Block => "block",
+                    CoroutineSource::Closure => "closure",
+                    CoroutineSource::Fn => "fn",
+                }.into())
             },
         ),
         _ => {
             unreachable!(
-                "attempted to read unexpected property '{property_name}' on type 'Fn'"
+                "attempted to read unexpected property '{property_name}' on type 'AsyncBlock'"
             )
         }
     }
 }
 
-pub(super) fn resolve_inside_const_context_property<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_basic_block_property<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
-    _property_name: &str,
+    property_name: &str,
     _resolve_info: &ResolveInfo,
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
-    resolve_property_with(
-        contexts,
-        move |vertex| {
-            let hir_id = vertex
-                .hir_id()
-                .expect("vertex is not a 'Node'");
+    match property_name {
+        "index" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let (_, block) = vertex
+                    .as_basic_block()
+                    .expect("vertex is not variant 'BasicBlock'");
 
-            let inside = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                let hir = ctxt.hir();
-                hir.is_inside_const_context(hir_id)
-            });
+                FieldValue::Uint64(block.as_u32().into())
+            },
+        ),
+        "is_cleanup" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let (&origin, &block) = vertex
+                    .as_basic_block()
+                    .expect("vertex is not variant 'BasicBlock'");
 
-            FieldValue::Boolean(inside)
-        },
-    )
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    origin.body(ctxt).basic_blocks[block].is_cleanup.into()
+                })
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'BasicBlock'"
+            )
+        }
+    }
 }
 
-pub(super) fn resolve_item_property<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_binary_property<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     property_name: &str,
     _resolve_info: &ResolveInfo,
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
-        "ident" => resolve_property_with(
+        "is_overloaded" => resolve_property_with(
             contexts,
             move |vertex| {
-                if let Some(item_id) = vertex.item_id() {
-                    shared::resolve_ident(item_id, adapter)
-                } else {
-                    unimplemented!("{vertex:?} is not an Item")
-                }
+                let hir_id = vertex
+                    .as_binary()
+                    .expect("vertex is not variant 'Binary'");
+                shared::resolve_is_overloaded(*hir_id, adapter)
+            },
+        ),
+        "operator" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .as_binary()
+                    .expect("vertex is not variant 'Binary'");
+
+                let operator = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let expr = ctxt.hir().expect_expr(*hir_id);
+                    let ExprKind::Binary(op, ..) = expr.kind else {
+                        unimplemented!("expr was not of type Binary: {expr:#?}")
+                    };
+                    op.node.as_str()
+                });
+
+                FieldValue::String(operator.into())
             },
         ),
         _ => {
             unreachable!(
-                "attempted to read unexpected property '{property_name}' on type 'Item'"
+                "attempted to read unexpected property '{property_name}' on type 'Binary'"
             )
         }
     }
 }
 
+pub(super) fn resolve_block_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "is_unsafe" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let hir_id = *vertex
+                    .as_block()
+                    .expect("vertex is not variant 'Block'");
 
+                let is_unsafe = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    is_unsafe_block(ctxt.hir().expect_expr(hir_id))
+                });
 
-pub(super) fn resolve_node_property<'a, V: AsVertex<Vertex> + 'a>(
-    _contexts: ContextIterator<'a, V>,
+                FieldValue::Boolean(is_unsafe)
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Block'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_borrow_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
     property_name: &str,
     _resolve_info: &ResolveInfo,
-    _adapter: &'a Adapter,
+    adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl for<'tcx> FnOnce(TyCtxt<'tcx>, &mir::Body<'tcx>, &BorrowData<'tcx>) -> FieldValue,
+    ) -> FieldValue {
+        let (&def_id, &borrow) = vertex.as_borrow().expect("vertex is not a Borrow");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+            let facts = borrowck_facts(ctxt, def_id).expect("borrowck facts should have been kept");
+            f(ctxt, &facts.body, &facts.borrow_set[borrow])
+        })
+    }
+
     match property_name {
-        
+        "activation_span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, body, borrow| {
+                match borrow.activation_location {
+                    TwoPhaseActivation::ActivatedAt(location) => {
+                        adapter.span_to_string(ctxt, body.source_info(location).span).into()
+                    }
+                    TwoPhaseActivation::NotTwoPhase | TwoPhaseActivation::NotActivated => FieldValue::Null,
+                }
+            }),
+        ),
+        "assigned_place" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, borrow| {
+                format!("{:?}", borrow.assigned_place).into()
+            }),
+        ),
+        "borrowed_place" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, borrow| {
+                format!("{:?}", borrow.borrowed_place).into()
+            }),
+        ),
+        "index" => resolve_property_with(
+            contexts,
+            |vertex| {
+                let (_, borrow) = vertex.as_borrow().expect("vertex is not a Borrow");
+                FieldValue::Uint64(borrow.as_u32().into())
+            },
+        ),
+        "is_two_phase" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, borrow| {
+                (borrow.activation_location != TwoPhaseActivation::NotTwoPhase).into()
+            }),
+        ),
+        "kind" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, borrow| {
+                match borrow.kind {
+                    mir::BorrowKind::Shared => "shared",
+                    mir::BorrowKind::Fake => "fake",
+                    mir::BorrowKind::Mut { kind: mir::MutBorrowKind::ClosureCapture } => "unique",
+                    mir::BorrowKind::Mut { .. } => "mut",
+                }.into()
+            }),
+        ),
+        "region" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, borrow| {
+                format!("{:?}", borrow.region).into()
+            }),
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, body, borrow| {
+                adapter.span_to_string(ctxt, body.source_info(borrow.reserve_location).span).into()
+            }),
+        ),
         _ => {
             unreachable!(
-                "attempted to read unexpected property '{property_name}' on type 'Node'"
+                "attempted to read unexpected property '{property_name}' on type 'Borrow'"
             )
         }
     }
 }
 
-pub(super) fn resolve_stability_property<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_borrow_adjustment_property<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     property_name: &str,
     _resolve_info: &ResolveInfo,
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
-        "feature" => resolve_property_with(
+        "is_mut" => resolve_property_with(
             contexts,
             move |vertex| {
-                let def_id = vertex
-                    .as_stability()
-                    .expect("vertex is not variant 'Stability'");
+                let (hir_id, index) = vertex
+                    .adjustment()
+                    .expect("vertex is not an Adjustment");
 
-                let string = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    ctxt
-                        .lookup_stability(def_id)
-                        .expect("def_id should have a Stability struct")
-                        .feature
-                        .to_string()
+                let is_mut = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let typeck = ctxt.typeck(ctxt.hir().enclosing_body_owner(hir_id));
+                    match typeck.adjustments()[hir_id][index].kind {
+                        Adjust::Borrow(AutoBorrow::Ref(_, mutability)) => {
+                            matches!(mutability, AutoBorrowMutability::Mut { .. })
+                        }
+                        Adjust::Borrow(AutoBorrow::RawPtr(mutability)) => mutability.is_mut(),
+                        _ => unreachable!("adjustment is not a borrow"),
+                    }
                 });
 
-                FieldValue::String(string.into())
+                FieldValue::Boolean(is_mut)
             },
         ),
-        "stable" => resolve_property_with(
+        "is_raw" => resolve_property_with(
             contexts,
             move |vertex| {
-                let def_id = vertex
-                    .as_stability()
-                    .expect("vertex is not variant 'Stability'");
+                let (hir_id, index) = vertex
+                    .adjustment()
+                    .expect("vertex is not an Adjustment");
 
-                let stable = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    ctxt
-                        .lookup_stability(def_id)
-                        .expect("def_id should have a Stability struct")
-                        .is_stable()
+                let is_raw = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let typeck = ctxt.typeck(ctxt.hir().enclosing_body_owner(hir_id));
+                    matches!(typeck.adjustments()[hir_id][index].kind, Adjust::Borrow(AutoBorrow::RawPtr(..)))
                 });
 
-                FieldValue::Boolean(stable)
+                FieldValue::Boolean(is_raw)
             },
         ),
-        "reason" => resolve_property_with(
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'BorrowAdjustment'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_const_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "eval_error" => resolve_property_with(
             contexts,
             move |vertex| {
-                let def_id = vertex
-                    .as_stability()
-                    .expect("vertex is not variant 'Stability'");
-
-                let reason = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let stability = ctxt
-                        .lookup_stability(def_id)
-                        .expect("def_id should have a Stability struct");
-                    if let StabilityLevel::Unstable { reason, .. }  = stability.level {
-                        reason.to_opt_reason()
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(reason) = reason {
-                    FieldValue::String(reason.to_string().into())
-                } else {
-                    FieldValue::NULL
-                }
+                let item_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'");
+                shared::resolve_eval_error(item_id, adapter)
             },
         ),
-        "issue" => resolve_property_with(
+        "docs" => resolve_property_with(
             contexts,
             move |vertex| {
-                let def_id = vertex
-                    .as_stability()
-                    .expect("vertex is not variant 'Stability'");
-
-                let issue = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let stability = ctxt
-                        .lookup_stability(def_id)
-                        .expect("def_id should have a Stability struct");
-                    if let StabilityLevel::Unstable { issue, .. }  = stability.level {
-                        issue
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(issue_number) = issue {
-                    let issue_number: u32 = issue_number.into();
-                    FieldValue::Uint64(issue_number as u64)
-                } else {
-                    FieldValue::NULL
-                }
+                let item_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'");
+                shared::resolve_docs(item_id.owner_id.to_def_id(), adapter)
             },
         ),
-        "soft" => resolve_property_with(
+        "ident" => resolve_property_with(
             contexts,
             move |vertex| {
-                let def_id = vertex
-                    .as_stability()
-                    .expect("vertex is not variant 'Stability'");
-
-                let soft = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let stability = ctxt
-                        .lookup_stability(def_id)
-                        .expect("def_id should have a Stability struct");
-                    if let StabilityLevel::Unstable { is_soft, .. }  = stability.level {
-                        Some(is_soft)
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(is_soft) = soft {
-                    FieldValue::Boolean(is_soft.into())
+                if let Some(item_id) = vertex.item_id() {
+                    shared::resolve_ident(item_id, adapter)
                 } else {
-                    FieldValue::NULL
+                    unimplemented!("{vertex:?} is not an Item")
                 }
             },
         ),
-        "implied_by" => resolve_property_with(
+        "effective_visibility" => resolve_property_with(
             contexts,
             move |vertex| {
                 let def_id = vertex
-                    .as_stability()
-                    .expect("vertex is not variant 'Stability'");
-
-                let soft = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let stability = ctxt
-                        .lookup_stability(def_id)
-                        .expect("def_id should have a Stability struct");
-                    if let StabilityLevel::Unstable { implied_by, .. }  = stability.level {
-                        implied_by
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(is_soft) = soft {
-                    FieldValue::String(is_soft.to_string().into())
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_effective_visibility(def_id, adapter)
+            },
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                if let Some(item_id) = vertex.item_id() {
+                    shared::resolve_span(item_id, adapter)
                 } else {
-                    FieldValue::NULL
+                    unimplemented!("{vertex:?} is not an Item")
                 }
             },
         ),
-        "since" => resolve_property_with(
+        "visibility" => resolve_property_with(
             contexts,
             move |vertex| {
                 let def_id = vertex
-                    .as_stability()
-                    .expect("vertex is not variant 'Stability'");
-
-                let version = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let stability = ctxt
-                        .lookup_stability(def_id)
-                        .expect("def_id should have a Stability struct");
-                    if let StabilityLevel::Stable { since, .. }  = stability.level {
-                        match since {
-                            rustc_attr::StableSince::Version(version) => Some(format!("{version}")),
-                            _ => None,
-                        }
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(ver_number) = version {
-                    FieldValue::String(ver_number.into())
-                } else {
-                    FieldValue::NULL
-                }
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_visibility(def_id, adapter)
             },
         ),
-        "allowed_through_unstable_modules" => resolve_property_with(
+        "visible_in" => resolve_property_with(
             contexts,
             move |vertex| {
                 let def_id = vertex
-                    .as_stability()
-                    .expect("vertex is not variant 'Stability'");
-
-                let allowed = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let stability = ctxt
-                        .lookup_stability(def_id)
-                        .expect("def_id should have a Stability struct");
-                    if let StabilityLevel::Stable { allowed_through_unstable_modules, .. }  = stability.level {
-                        Some(allowed_through_unstable_modules)
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(allowed) = allowed {
-                    FieldValue::Boolean(allowed)
-                } else {
-                    FieldValue::NULL
-                }
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_visible_in(def_id, adapter)
             },
         ),
         _ => {
             unreachable!(
-                "attempted to read unexpected property '{property_name}' on type 'Item'"
+                "attempted to read unexpected property '{property_name}' on type 'Const'"
             )
         }
     }
 }
 
-pub(super) fn resolve_const_stability_property<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_const_value_property<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     property_name: &str,
     _resolve_info: &ResolveInfo,
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
-        "feature" => resolve_property_with(
+        "bool_value" => resolve_property_with(
             contexts,
             move |vertex| {
-                let def_id = vertex
-                    .as_const_stability()
-                    .expect("vertex is not variant 'Stability'");
+                let (def_id, path) = vertex
+                    .as_const_value()
+                    .expect("vertex is not variant 'ConstValue'");
 
-                let string = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    ctxt
-                        .lookup_const_stability(def_id)
-                        .expect("def_id should have a ConstConstStability struct")
-                        .feature
-                        .to_string()
-                });
-
-                FieldValue::String(string.into())
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    match evaluated_const_field(ctxt, adapter, *def_id, path) {
+                        Some((value, ty)) if matches!(ty.kind(), ty::Bool) => {
+                            const_scalar_to_field_value(ctxt, value, ty)
+                        }
+                        _ => FieldValue::Null,
+                    }
+                })
             },
         ),
-        "promotable" => resolve_property_with(
+        "float_value" => resolve_property_with(
             contexts,
             move |vertex| {
-                let def_id = vertex
-                    .as_const_stability()
-                    .expect("vertex is not variant 'Stability'");
+                let (def_id, path) = vertex
+                    .as_const_value()
+                    .expect("vertex is not variant 'ConstValue'");
 
-                let stable = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    ctxt
-                        .lookup_const_stability(def_id)
-                        .expect("def_id should have a ConstConstStability struct")
-                        .is_const_stable()
-                });
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    match evaluated_const_field(ctxt, adapter, *def_id, path) {
+                        Some((value, ty)) if matches!(ty.kind(), ty::Float(_)) => {
+                            const_scalar_to_field_value(ctxt, value, ty)
+                        }
+                        _ => FieldValue::Null,
+                    }
+                })
+            },
+        ),
+        "int_value" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let (def_id, path) = vertex
+                    .as_const_value()
+                    .expect("vertex is not variant 'ConstValue'");
 
-                FieldValue::Boolean(stable)
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    match evaluated_const_field(ctxt, adapter, *def_id, path) {
+                        Some((value, ty)) if matches!(ty.kind(), ty::Int(_) | ty::Uint(_)) => {
+                            const_scalar_to_field_value(ctxt, value, ty)
+                        }
+                        _ => FieldValue::Null,
+                    }
+                })
             },
         ),
-        "stable" => resolve_property_with(
+        "string_value" => resolve_property_with(
             contexts,
             move |vertex| {
-                let def_id = vertex
-                    .as_const_stability()
-                    .expect("vertex is not variant 'Stability'");
+                let (def_id, path) = vertex
+                    .as_const_value()
+                    .expect("vertex is not variant 'ConstValue'");
 
-                let promotable = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    ctxt
-                        .lookup_const_stability(def_id)
-                        .expect("def_id should have a ConstConstStability struct")
-                        .promotable
-                });
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    match evaluated_const_field(ctxt, adapter, *def_id, path) {
+                        Some((value, ty)) if matches!(ty.kind(), ty::Char | ty::Ref(..)) => {
+                            const_scalar_to_field_value(ctxt, value, ty)
+                        }
+                        _ => FieldValue::Null,
+                    }
+                })
+            },
+        ),
+        "variant" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let (def_id, path) = vertex
+                    .as_const_value()
+                    .expect("vertex is not variant 'ConstValue'");
 
-                FieldValue::Boolean(promotable)
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let variant = evaluated_const_field(ctxt, adapter, *def_id, path)
+                        .and_then(|(value, ty)| {
+                            let ty::Adt(adt_def, _) = ty.kind() else {
+                                return None;
+                            };
+                            let variant = destructure_const(ctxt, value, ty)?.variant?;
+                            adt_def.is_enum().then(|| adt_def.variant(variant).name)
+                        });
+                    variant.map_or(FieldValue::Null, |name| FieldValue::String(name.as_str().into()))
+                })
             },
         ),
-        "reason" => resolve_property_with(
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'ConstValue'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_coroutine_layout_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "size" => resolve_property_with(
             contexts,
             move |vertex| {
-                let def_id = vertex
-                    .as_const_stability()
-                    .expect("vertex is not variant 'Stability'");
-
-                let reason = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let stability = ctxt
-                        .lookup_const_stability(def_id)
-                        .expect("def_id should have a ConstConstStability struct");
-                    if let StabilityLevel::Unstable { reason, .. }  = stability.level {
-                        reason.to_opt_reason()
-                    } else {
-                        None
-                    }
+                let def_id = *vertex
+                    .as_coroutine_layout()
+                    .expect("vertex is not variant 'CoroutineLayout'");
+
+                let size = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let ty = ctxt.type_of(def_id).instantiate_identity();
+                    ctxt
+                        .layout_of(ctxt.param_env(def_id).and(ty))
+                        .ok()
+                        .map(|layout| layout.size.bytes())
                 });
 
-                if let Some(reason) = reason {
-                    FieldValue::String(reason.to_string().into())
+                if let Some(size) = size {
+                    FieldValue::Uint64(size)
                 } else {
                     FieldValue::NULL
                 }
             },
         ),
-        "issue" => resolve_property_with(
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'CoroutineLayout'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_coroutine_saved_local_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "index" => resolve_property_with(
             contexts,
             move |vertex| {
-                let def_id = vertex
-                    .as_const_stability()
-                    .expect("vertex is not variant 'Stability'");
-
-                let issue = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let stability = ctxt
-                        .lookup_const_stability(def_id)
-                        .expect("def_id should have a ConstConstStability struct");
-                    if let StabilityLevel::Unstable { issue, .. }  = stability.level {
-                        issue
-                    } else {
-                        None
-                    }
-                });
+                let (_, local) = vertex
+                    .as_coroutine_saved_local()
+                    .expect("vertex is not variant 'CoroutineSavedLocal'");
 
-                if let Some(issue_number) = issue {
-                    let issue_number: u32 = issue_number.into();
-                    FieldValue::Uint64(issue_number as u64)
-                } else {
-                    FieldValue::NULL
-                }
+                FieldValue::Uint64(local.as_u32().into())
             },
         ),
-        "soft" => resolve_property_with(
+        "name" => resolve_property_with(
             contexts,
             move |vertex| {
-                let def_id = vertex
-                    .as_const_stability()
-                    .expect("vertex is not variant 'Stability'");
-
-                let soft = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let stability = ctxt
-                        .lookup_const_stability(def_id)
-                        .expect("def_id should have a ConstConstStability struct");
-                    if let StabilityLevel::Unstable { is_soft, .. }  = stability.level {
-                        Some(is_soft)
-                    } else {
-                        None
-                    }
+                let (def_id, local) = vertex
+                    .as_coroutine_saved_local()
+                    .expect("vertex is not variant 'CoroutineSavedLocal'");
+
+                let name = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt
+                        .coroutine_layout(*def_id)
+                        .expect("coroutine should have a layout")
+                        .field_names[*local]
+                        .map(|name| name.to_string())
                 });
 
-                if let Some(is_soft) = soft {
-                    FieldValue::Boolean(is_soft.into())
+                if let Some(name) = name {
+                    FieldValue::String(name.into())
                 } else {
                     FieldValue::NULL
                 }
             },
         ),
-        "implied_by" => resolve_property_with(
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'CoroutineSavedLocal'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_crate_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl FnOnce(TyCtxt<'_>, CrateNum) -> FieldValue,
+    ) -> FieldValue {
+        let krate = *vertex
+            .as_crate()
+            .expect("vertex is not variant 'Crate'");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| f(ctxt, krate))
+    }
+
+    // How the crate is compiled is only known for the local crate, dependencies were
+    // compiled separately.
+    match property_name {
+        "cfg" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, krate| {
+                if krate != LOCAL_CRATE {
+                    return Vec::<String>::new().into();
+                }
+                ctxt.sess.parse_sess.config
+                    .iter()
+                    .map(|(name, value)| match value {
+                        Some(value) => format!("{name}=\"{value}\""),
+                        None => name.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .into()
+            }),
+        ),
+        "crate_type" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, krate| {
+                if krate != LOCAL_CRATE {
+                    return Vec::<String>::new().into();
+                }
+                ctxt.crate_types().iter().map(|crate_type| crate_type.to_string()).collect::<Vec<_>>().into()
+            }),
+        ),
+        "dep_kind" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, krate| {
+                if krate == LOCAL_CRATE {
+                    return FieldValue::NULL;
+                }
+                let dep_kind = match ctxt.dep_kind(krate) {
+                    CrateDepKind::MacrosOnly => "macros_only",
+                    CrateDepKind::Implicit => "implicit",
+                    CrateDepKind::Explicit => "explicit",
+                };
+                FieldValue::String(dep_kind.into())
+            }),
+        ),
+        "edition" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, krate| {
+                if krate != LOCAL_CRATE {
+                    return FieldValue::NULL;
+                }
+                FieldValue::String(ctxt.sess.edition().to_string().into())
+            }),
+        ),
+        "is_private_dep" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, krate| {
+                FieldValue::Boolean(krate != LOCAL_CRATE && ctxt.is_private_dep(krate))
+            }),
+        ),
+        "name" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, krate| {
+                FieldValue::String(ctxt.crate_name(krate).to_string().into())
+            }),
+        ),
+        "stable_crate_id" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, krate| {
+                FieldValue::String(format!("{:016x}", ctxt.stable_crate_id(krate).as_u64()).into())
+            }),
+        ),
+        "target_triple" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, krate| {
+                if krate != LOCAL_CRATE {
+                    return FieldValue::NULL;
+                }
+                FieldValue::String(ctxt.sess.opts.target_triple.triple().into())
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Crate'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_def_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "crate_name" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_def()
+                    .expect("vertex is not variant 'Def'");
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.crate_name(def_id.krate).as_str().into()
+                })
+            },
+        ),
+        "diagnostic_item" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_def()
+                    .expect("vertex is not variant 'Def'");
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.get_diagnostic_name(def_id).map(|name| name.to_string()).into()
+                })
+            },
+        ),
+        "docs" => resolve_property_with(
             contexts,
             move |vertex| {
                 let def_id = vertex
-                    .as_const_stability()
-                    .expect("vertex is not variant 'Stability'");
-
-                let soft = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let stability = ctxt
-                        .lookup_const_stability(def_id)
-                        .expect("def_id should have a ConstConstStability struct");
-                    if let StabilityLevel::Unstable { implied_by, .. }  = stability.level {
-                        implied_by
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(is_soft) = soft {
-                    FieldValue::String(is_soft.to_string().into())
-                } else {
-                    FieldValue::NULL
-                }
+                    .as_def()
+                    .expect("vertex is not variant 'Def'");
+                shared::resolve_docs(*def_id, adapter)
             },
         ),
-        "since" => resolve_property_with(
+        "is_local" => resolve_property_with(
             contexts,
             move |vertex| {
                 let def_id = vertex
-                    .as_const_stability()
-                    .expect("vertex is not variant 'Stability'");
-
-                let version = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let stability = ctxt
-                        .lookup_const_stability(def_id)
-                        .expect("def_id should have a ConstConstStability struct");
-                    if let StabilityLevel::Stable { since, .. }  = stability.level {
-                        match since {
-                            rustc_attr::StableSince::Version(version) => Some(format!("{version}")),
-                            _ => None,
+                    .as_def()
+                    .expect("vertex is not variant 'Def'");
+                def_id.is_local().into()
+            },
+        ),
+        "kind" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_def()
+                    .expect("vertex is not variant 'Def'");
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.def_kind(def_id).descr(def_id).into()
+                })
+            },
+        ),
+        "ident" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                if let Some(def_id) = vertex.def_id() {
+                    adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                        if let Some(ident) = ctxt.opt_item_ident(def_id) {
+                            FieldValue::String(ident.as_str().into())
+                        } else {
+                            FieldValue::NULL
                         }
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(ver_number) = version {
-                    FieldValue::String(ver_number.into())
+                    })
                 } else {
-                    FieldValue::NULL
+                    unimplemented!("{vertex:?} is not a Def")
                 }
             },
         ),
-        "allowed_through_unstable_modules" => resolve_property_with(
+        "path" => resolve_property_with(
             contexts,
             move |vertex| {
-                let def_id = vertex
-                    .as_const_stability()
-                    .expect("vertex is not variant 'Stability'");
-
-                let allowed = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let stability = ctxt
-                        .lookup_const_stability(def_id)
-                        .expect("def_id should have a ConstConstStability struct");
-                    if let StabilityLevel::Stable { allowed_through_unstable_modules, .. }  = stability.level {
-                        Some(allowed_through_unstable_modules)
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(allowed) = allowed {
-                    FieldValue::Boolean(allowed)
+                if let Some(def_id) = vertex.def_id() {
+                    adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                        FieldValue::String(ctxt.def_path_str(def_id).into())
+                    })
                 } else {
-                    FieldValue::NULL
+                    unimplemented!("{vertex:?} is not a Def")
                 }
             },
         ),
+        "lang_item" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_def()
+                    .expect("vertex is not variant 'Def'");
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.lang_items()
+                        .iter()
+                        .find(|(_, id)| *id == def_id)
+                        .map(|(item, _)| item.name().to_string())
+                        .into()
+                })
+            },
+        ),
+        "path_segment" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_def()
+                    .expect("vertex is not variant 'Def'");
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    std::iter::once(ctxt.crate_name(def_id.krate).to_string())
+                        .chain(ctxt.def_path(def_id).data.iter().map(|data| data.to_string()))
+                        .collect::<Vec<_>>()
+                        .into()
+                })
+            },
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_def()
+                    .expect("vertex is not variant 'Def'");
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    adapter.span_to_string(ctxt, ctxt.def_span(def_id)).into()
+                })
+            },
+        ),
+        "effective_visibility" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_def()
+                    .expect("vertex is not variant 'Def'");
+                shared::resolve_effective_visibility(def_id, adapter)
+            },
+        ),
+        "visibility" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_def()
+                    .expect("vertex is not variant 'Def'");
+                shared::resolve_visibility(def_id, adapter)
+            },
+        ),
+        "visible_in" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = *vertex
+                    .as_def()
+                    .expect("vertex is not variant 'Def'");
+                shared::resolve_visible_in(def_id, adapter)
+            },
+        ),
         _ => {
             unreachable!(
-                "attempted to read unexpected property '{property_name}' on type 'Item'"
+                "attempted to read unexpected property '{property_name}' on type 'Def'"
             )
         }
     }
 }
 
-pub(super) fn resolve_ty_property<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_deprecation_property<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     property_name: &str,
     _resolve_info: &ResolveInfo,
     adapter: &'a Adapter,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl FnOnce(&Deprecation) -> FieldValue,
+    ) -> FieldValue {
+        let def_id = *vertex
+            .as_deprecation()
+            .expect("vertex is not variant 'Deprecation'");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+            let entry = ctxt
+                .lookup_deprecation_entry(def_id)
+                .expect("def_id should have a DeprecationEntry");
+            f(&entry.attr)
+        })
+    }
+
+    fn symbol_field(symbol: Option<Symbol>) -> FieldValue {
+        symbol.map_or(FieldValue::Null, |symbol| FieldValue::String(symbol.to_string().into()))
+    }
+
     match property_name {
-        "as_string" => resolve_property_with(
+        "is_in_effect" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |deprecation| {
+                FieldValue::Boolean(deprecation.is_in_effect())
+            }),
+        ),
+        "is_rustc_deprecation" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |deprecation| {
+                FieldValue::Boolean(deprecation.is_since_rustc_version())
+            }),
+        ),
+        "note" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |deprecation| {
+                symbol_field(deprecation.note)
+            }),
+        ),
+        "since" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |deprecation| {
+                match deprecation.since {
+                    DeprecatedSince::RustcVersion(version) => FieldValue::String(version.to_string().into()),
+                    DeprecatedSince::Future => FieldValue::String("TBD".into()),
+                    DeprecatedSince::NonStandard(since) => FieldValue::String(since.to_string().into()),
+                    DeprecatedSince::Unspecified | DeprecatedSince::Err => FieldValue::NULL,
+                }
+            }),
+        ),
+        "suggestion" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |deprecation| {
+                symbol_field(deprecation.suggestion)
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Deprecation'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_deref_adjustment_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "is_mut" => resolve_property_with(
             contexts,
             move |vertex| {
-                let Vertex::Ty(hir_id) = vertex else {
-                    unimplemented!("vertex is not Ty: {vertex:#?}")
-                };
-                let string: String = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
-                    let hir = ctxt.hir();
-                    let def_id = hir.enclosing_body_owner(*hir_id);
-                    ctxt.typeck(def_id).node_type(*hir_id).to_string()
+                let (hir_id, index) = vertex
+                    .adjustment()
+                    .expect("vertex is not an Adjustment");
+
+                let is_mut = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let typeck = ctxt.typeck(ctxt.hir().enclosing_body_owner(hir_id));
+                    match typeck.adjustments()[hir_id][index].kind {
+                        Adjust::Deref(overloaded) => overloaded.map(|overloaded| overloaded.mutbl.is_mut()),
+                        _ => unreachable!("adjustment is not a deref"),
+                    }
                 });
-                FieldValue::String(string.into())
+
+                if let Some(is_mut) = is_mut {
+                    FieldValue::Boolean(is_mut)
+                } else {
+                    FieldValue::NULL
+                }
             },
         ),
         _ => {
             unreachable!(
-                "attempted to read unexpected property '{property_name}' on type 'Ty'"
+                "attempted to read unexpected property '{property_name}' on type 'DerefAdjustment'"
             )
         }
     }
 }
 
-pub(crate) mod shared {
-    use rustc_hir::ItemId;
-    use trustfall::FieldValue;
+pub(super) fn resolve_doc_link_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "is_resolved" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let (def_id, index) = vertex
+                    .as_doc_link()
+                    .expect("vertex is not variant 'DocLink'");
 
-    use crate::adapter::Adapter;
+                let is_resolved = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let link = &doc_links(ctxt.get_attrs_unchecked(*def_id))[*index];
+                    resolve_doc_link(ctxt, *def_id, link).is_some()
+                });
 
-    pub fn resolve_ident<'a>(
-        item_id: ItemId,
-        adapter: &'a Adapter,
+                FieldValue::Boolean(is_resolved)
+            },
+        ),
+        "link" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let (def_id, index) = vertex
+                    .as_doc_link()
+                    .expect("vertex is not variant 'DocLink'");
+
+                let link = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    doc_links(ctxt.get_attrs_unchecked(*def_id)).swap_remove(*index)
+                });
+
+                FieldValue::String(link.into())
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'DocLink'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_feature_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl FnOnce(TyCtxt<'_>, bool, Span, Option<Symbol>) -> FieldValue,
+    ) -> FieldValue {
+        let feature = *vertex
+            .as_feature()
+            .expect("vertex is not variant 'Feature'");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+            let (is_lang, span, since) = declared_feature(ctxt, feature);
+            f(ctxt, is_lang, span, since)
+        })
+    }
+
+    match property_name {
+        "kind" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, is_lang, _, _| {
+                FieldValue::String(if is_lang { "lang" } else { "lib" }.into())
+            }),
+        ),
+        "name" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let feature = vertex
+                    .as_feature()
+                    .expect("vertex is not variant 'Feature'");
+                FieldValue::String(feature.to_string().into())
+            },
+        ),
+        "since" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, _, since| {
+                since.map_or(FieldValue::NULL, |since| FieldValue::String(since.to_string().into()))
+            }),
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, _, span, _| {
+                FieldValue::String(adapter.span_to_string(ctxt, span).into())
+            }),
+        ),
+        "stable" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, _, since| {
+                FieldValue::Boolean(since.is_some())
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Feature'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_fn_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "docs" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'");
+                shared::resolve_docs(item_id.owner_id.to_def_id(), adapter)
+            },
+        ),
+        "ident" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                if let Some(item_id) = vertex.item_id() {
+                    shared::resolve_ident(item_id, adapter)
+                } else {
+                    unimplemented!("{vertex:?} is not an Item")
+                }
+            },
+        ),
+        "is_async" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'");
+
+                let is_async = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let sig = ctxt
+                        .hir()
+                        .fn_sig_by_hir_id(item_id.hir_id())
+                        .expect("vertex should be a function");
+                    sig.header.is_async()
+                });
+
+                FieldValue::Boolean(is_async)
+            },
+        ),
+        "is_unsafe" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'");
+
+                let is_unsafe = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let sig = ctxt
+                        .hir()
+                        .fn_sig_by_hir_id(item_id.hir_id())
+                        .expect("vertex should be a function");
+                    sig.header.is_unsafe()
+                });
+
+                FieldValue::Boolean(is_unsafe)
+            },
+        ),
+        "may_panic" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .def_id;
+
+                let may_panic = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    may_panic(ctxt, def_id)
+                });
+
+                FieldValue::Boolean(may_panic)
+            },
+        ),
+        "effective_visibility" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_effective_visibility(def_id, adapter)
+            },
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                if let Some(item_id) = vertex.item_id() {
+                    shared::resolve_span(item_id, adapter)
+                } else {
+                    unimplemented!("{vertex:?} is not an Item")
+                }
+            },
+        ),
+        "visibility" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_visibility(def_id, adapter)
+            },
+        ),
+        "visible_in" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_visible_in(def_id, adapter)
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Fn'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_impl_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "docs" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'");
+                shared::resolve_docs(item_id.owner_id.to_def_id(), adapter)
+            },
+        ),
+        "ident" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                if let Some(item_id) = vertex.item_id() {
+                    shared::resolve_ident(item_id, adapter)
+                } else {
+                    unimplemented!("{vertex:?} is not an Item")
+                }
+            },
+        ),
+        "is_unsafe" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let item_id = *vertex
+                    .as_impl()
+                    .expect("vertex is not variant 'Impl'");
+
+                let is_unsafe = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    ctxt.hir().item(item_id).expect_impl().unsafety == Unsafety::Unsafe
+                });
+
+                FieldValue::Boolean(is_unsafe)
+            },
+        ),
+        "effective_visibility" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_effective_visibility(def_id, adapter)
+            },
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                if let Some(item_id) = vertex.item_id() {
+                    shared::resolve_span(item_id, adapter)
+                } else {
+                    unimplemented!("{vertex:?} is not an Item")
+                }
+            },
+        ),
+        "visibility" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_visibility(def_id, adapter)
+            },
+        ),
+        "visible_in" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_visible_in(def_id, adapter)
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Impl'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_index_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "is_overloaded" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .as_index()
+                    .expect("vertex is not variant 'Index'");
+                shared::resolve_is_overloaded(*hir_id, adapter)
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Index'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_inside_const_context_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    _property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    resolve_property_with(
+        contexts,
+        move |vertex| {
+            let hir_id = vertex
+                .hir_id()
+                .expect("vertex is not a 'Node'");
+
+            let inside = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                let hir = ctxt.hir();
+                hir.is_inside_const_context(hir_id)
+            });
+
+            FieldValue::Boolean(inside)
+        },
+    )
+}
+
+pub(super) fn resolve_instance_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl for<'tcx> FnOnce(TyCtxt<'tcx>, MonoItem<'tcx>, MonoItemData, Symbol) -> FieldValue,
+    ) -> FieldValue {
+        let instance = *vertex.as_instance().expect("vertex is not an Instance");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+            let (item, data, codegen_unit) = adapter.mono_items(ctxt)[instance];
+            f(ctxt, item, data, codegen_unit)
+        })
+    }
+
+    match property_name {
+        "as_string" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, item, _, _| {
+                item.to_string().into()
+            }),
+        ),
+        "codegen_unit" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, _, codegen_unit| {
+                codegen_unit.to_string().into()
+            }),
+        ),
+        "is_inlined" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, data, _| data.inlined.into()),
+        ),
+        "kind" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, item, _, _| {
+                match item {
+                    MonoItem::Fn(instance) => variant_name(&instance.def),
+                    MonoItem::Static(_) => "Static".to_string(),
+                    MonoItem::GlobalAsm(_) => "GlobalAsm".to_string(),
+                }.into()
+            }),
+        ),
+        "linkage" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, data, _| {
+                format!("{:?}", data.linkage).into()
+            }),
+        ),
+        "size_estimate" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, data, _| {
+                FieldValue::Uint64(data.size_estimate as u64)
+            }),
+        ),
+        "symbol_name" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, item, _, _| {
+                item.symbol_name(ctxt).name.into()
+            }),
+        ),
+        "visibility" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, data, _| {
+                format!("{:?}", data.visibility).into()
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Instance'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_item_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "docs" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'");
+                shared::resolve_docs(item_id.owner_id.to_def_id(), adapter)
+            },
+        ),
+        "ident" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                if let Some(item_id) = vertex.item_id() {
+                    shared::resolve_ident(item_id, adapter)
+                } else {
+                    unimplemented!("{vertex:?} is not an Item")
+                }
+            },
+        ),
+        "effective_visibility" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_effective_visibility(def_id, adapter)
+            },
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                if let Some(item_id) = vertex.item_id() {
+                    shared::resolve_span(item_id, adapter)
+                } else {
+                    unimplemented!("{vertex:?} is not an Item")
+                }
+            },
+        ),
+        "visibility" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_visibility(def_id, adapter)
+            },
+        ),
+        "visible_in" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_visible_in(def_id, adapter)
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Item'"
+            )
+        }
+    }
+}
+
+
+
+pub(super) fn resolve_lit_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn lit_kind(vertex: &Vertex, adapter: &Adapter) -> LitKind {
+        let hir_id = vertex
+            .as_lit()
+            .expect("vertex is not variant 'Lit'");
+
+        adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+            let expr = ctxt.hir().expect_expr(*hir_id);
+            let ExprKind::Lit(lit) = expr.kind else {
+                unimplemented!("expr was not of type Lit: {expr:#?}")
+            };
+            lit.node.clone()
+        })
+    }
+
+    match property_name {
+        "kind" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let kind = match lit_kind(vertex, adapter) {
+                    LitKind::Str(..) => "str",
+                    LitKind::ByteStr(..) => "byte_str",
+                    LitKind::CStr(..) => "c_str",
+                    LitKind::Byte(_) => "byte",
+                    LitKind::Char(_) => "char",
+                    LitKind::Int(..) => "int",
+                    LitKind::Float(..) => "float",
+                    LitKind::Bool(_) => "bool",
+                    LitKind::Err => "err",
+                };
+                FieldValue::String(kind.into())
+            },
+        ),
+        "text" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .as_lit()
+                    .expect("vertex is not variant 'Lit'");
+
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let span = ctxt.hir().span(*hir_id);
+                    ctxt
+                        .sess
+                        .source_map()
+                        .span_to_snippet(span)
+                        .map_or(FieldValue::Null, |text| FieldValue::String(text.into()))
+                })
+            },
+        ),
+        "suffix" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let suffix = match lit_kind(vertex, adapter) {
+                    LitKind::Int(_, LitIntType::Signed(ty)) => Some(ty.name_str()),
+                    LitKind::Int(_, LitIntType::Unsigned(ty)) => Some(ty.name_str()),
+                    LitKind::Float(_, LitFloatType::Suffixed(ty)) => Some(ty.name_str()),
+                    _ => None,
+                };
+                suffix.map_or(FieldValue::Null, |suffix| FieldValue::String(suffix.into()))
+            },
+        ),
+        "int_value" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                match lit_kind(vertex, adapter) {
+                    LitKind::Int(value, _) => {
+                        if let Ok(value) = i64::try_from(value) {
+                            FieldValue::Int64(value)
+                        } else if let Ok(value) = u64::try_from(value) {
+                            FieldValue::Uint64(value)
+                        } else {
+                            FieldValue::Null
+                        }
+                    }
+                    LitKind::Byte(value) => FieldValue::Int64(value.into()),
+                    _ => FieldValue::Null,
+                }
+            },
+        ),
+        "float_value" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                match lit_kind(vertex, adapter) {
+                    LitKind::Float(symbol, _) => symbol
+                        .as_str()
+                        .replace('_', "")
+                        .parse::<f64>()
+                        .map_or(FieldValue::Null, FieldValue::Float64),
+                    _ => FieldValue::Null,
+                }
+            },
+        ),
+        "string_value" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let value = match lit_kind(vertex, adapter) {
+                    LitKind::Str(symbol, _) => Some(symbol.as_str().to_owned()),
+                    LitKind::Char(c) => Some(c.to_string()),
+                    LitKind::ByteStr(bytes, _) => String::from_utf8(bytes.to_vec()).ok(),
+                    LitKind::CStr(bytes, _) => {
+                        let bytes = bytes.strip_suffix(&[0]).unwrap_or(&bytes);
+                        String::from_utf8(bytes.to_vec()).ok()
+                    }
+                    _ => None,
+                };
+                value.map_or(FieldValue::Null, |value| FieldValue::String(value.into()))
+            },
+        ),
+        "bool_value" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                match lit_kind(vertex, adapter) {
+                    LitKind::Bool(value) => FieldValue::Boolean(value),
+                    _ => FieldValue::Null,
+                }
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Lit'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_meta_item_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl FnOnce(&NestedMetaItem) -> Option<String>,
+    ) -> FieldValue {
+        let (origin, path) = vertex
+            .as_meta_item()
+            .expect("vertex is not variant 'MetaItem'");
+
+        let value = adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+            let item = nested_meta_item(origin.attr(ctxt), path)
+                .expect("meta item should exist");
+            f(&item)
+        });
+        value.map_or(FieldValue::Null, |value| FieldValue::String(value.into()))
+    }
+
+    match property_name {
+        "literal" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |item| {
+                item.lit().map(|lit| lit.symbol.to_string())
+            }),
+        ),
+        "path" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |item| {
+                item.meta_item().map(|meta| pprust::path_to_string(&meta.path))
+            }),
+        ),
+        "value_str" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |item| {
+                item.value_str().map(|value| value.to_string())
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'MetaItem'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_mir_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl FnOnce(TyCtxt<'_>, &mir::Body<'_>) -> FieldValue,
+    ) -> FieldValue {
+        let origin = *vertex
+            .as_mir()
+            .expect("vertex is not variant 'Mir'");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| f(ctxt, origin.body(ctxt)))
+    }
+
+    match property_name {
+        "arg_count" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, body| {
+                FieldValue::Uint64(body.arg_count as u64)
+            }),
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, body| {
+                adapter.span_to_string(ctxt, body.span).into()
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Mir'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_mir_local_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl FnOnce(TyCtxt<'_>, &mir::Body<'_>, mir::Local) -> FieldValue,
+    ) -> FieldValue {
+        let (&origin, &local) = vertex
+            .as_mir_local()
+            .expect("vertex is not variant 'MirLocal'");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| f(ctxt, origin.body(ctxt), local))
+    }
+
+    match property_name {
+        "drop_order" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, body, local| {
+                match mir_drops(body).get(&local) {
+                    Some(&(_, order)) => FieldValue::Uint64(order as u64),
+                    None => FieldValue::Null,
+                }
+            }),
+        ),
+        "drop_span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, body, local| {
+                mir_drops(body)
+                    .get(&local)
+                    .map(|&(span, _)| adapter.span_to_string(ctxt, span))
+                    .into()
+            }),
+        ),
+        "index" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let (_, local) = vertex
+                    .as_mir_local()
+                    .expect("vertex is not variant 'MirLocal'");
+
+                FieldValue::Uint64(local.as_u32().into())
+            },
+        ),
+        "is_mut" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, body, local| {
+                body.local_decls[local].mutability.is_mut().into()
+            }),
+        ),
+        "kind" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, body, local| {
+                match body.local_kind(local) {
+                    mir::LocalKind::ReturnPointer => "return",
+                    mir::LocalKind::Arg => "arg",
+                    mir::LocalKind::Temp if body.local_decls[local].is_user_variable() => "user",
+                    mir::LocalKind::Temp => "temp",
+                }.into()
+            }),
+        ),
+        "name" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, body, local| {
+                body.var_debug_info
+                    .iter()
+                    .find(|info| matches!(
+                        info.value,
+                        mir::VarDebugInfoContents::Place(place) if place.as_local() == Some(local)
+                    ))
+                    .map(|info| info.name.to_string())
+                    .into()
+            }),
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, body, local| {
+                adapter.span_to_string(ctxt, body.local_decls[local].source_info.span).into()
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'MirLocal'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_mir_statement_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl FnOnce(TyCtxt<'_>, &mir::Statement<'_>) -> FieldValue,
+    ) -> FieldValue {
+        let (&origin, &location) = vertex
+            .as_mir_statement()
+            .expect("vertex is not variant 'MirStatement'");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+            let body = origin.body(ctxt);
+            f(ctxt, &body.basic_blocks[location.block].statements[location.statement_index])
+        })
+    }
+
+    match property_name {
+        "as_string" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, statement| {
+                format!("{statement:?}").into()
+            }),
+        ),
+        "index" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let (_, location) = vertex
+                    .as_mir_statement()
+                    .expect("vertex is not variant 'MirStatement'");
+
+                FieldValue::Uint64(location.statement_index as u64)
+            },
+        ),
+        "kind" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, statement| {
+                statement.kind.name().into()
+            }),
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, statement| {
+                adapter.span_to_string(ctxt, statement.source_info.span).into()
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'MirStatement'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_move_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl for<'tcx> FnOnce(TyCtxt<'tcx>, &mir::Body<'tcx>, mir::Location, mir::Place<'tcx>) -> FieldValue,
+    ) -> FieldValue {
+        let (&def_id, &index) = vertex.as_move().expect("vertex is not a Move");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+            let facts = borrowck_facts(ctxt, def_id).expect("borrowck facts should have been kept");
+            let (location, place) = mir_moves(&facts.body)[index];
+            f(ctxt, &facts.body, location, place)
+        })
+    }
+
+    match property_name {
+        "place" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, _, place| {
+                format!("{place:?}").into()
+            }),
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, body, location, _| {
+                adapter.span_to_string(ctxt, body.source_info(location).span).into()
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Move'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_node_property<'a, V: AsVertex<Vertex> + 'a>(
+    _contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    _adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    unreachable!(
+        "attempted to read unexpected property '{property_name}' on type 'Node'"
+    )
+}
+
+pub(super) fn resolve_panic_site_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "kind" => resolve_property_with(
+            contexts,
+            |vertex| {
+                let (_, _, kind) = vertex.as_panic_site().expect("vertex is not a PanicSite");
+                kind.as_str().into()
+            },
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let (&origin, &block, _) = vertex.as_panic_site().expect("vertex is not a PanicSite");
+
+                adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+                    let span = origin.body(ctxt).basic_blocks[block].terminator().source_info.span;
+                    adapter.span_to_string(ctxt, span).into()
+                })
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'PanicSite'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_pointer_adjustment_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "coercion" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let (hir_id, index) = vertex
+                    .adjustment()
+                    .expect("vertex is not an Adjustment");
+
+                let coercion = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let typeck = ctxt.typeck(ctxt.hir().enclosing_body_owner(hir_id));
+                    let Adjust::Pointer(coercion) = typeck.adjustments()[hir_id][index].kind else {
+                        unreachable!("adjustment is not a pointer coercion")
+                    };
+                    coercion
+                });
+
+                FieldValue::String(match coercion {
+                    PointerCoercion::ReifyFnPointer => "ReifyFnPointer",
+                    PointerCoercion::UnsafeFnPointer => "UnsafeFnPointer",
+                    PointerCoercion::ClosureFnPointer(..) => "ClosureFnPointer",
+                    PointerCoercion::MutToConstPointer => "MutToConstPointer",
+                    PointerCoercion::ArrayToPointer => "ArrayToPointer",
+                    PointerCoercion::Unsize => "Unsize",
+                }.into())
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'PointerAdjustment'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_region_constraint_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl for<'tcx> FnOnce(TyCtxt<'tcx>, OutlivesConstraint<'tcx>) -> FieldValue,
+    ) -> FieldValue {
+        let (&def_id, &index) = vertex
+            .as_region_constraint()
+            .expect("vertex is not a RegionConstraint");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+            let facts = borrowck_facts(ctxt, def_id).expect("borrowck facts should have been kept");
+            let constraint = facts.region_inference_context
+                .outlives_constraints()
+                .nth(index)
+                .expect("constraint index out of bounds");
+            f(ctxt, constraint)
+        })
+    }
+
+    match property_name {
+        "category" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, constraint| {
+                variant_name(&constraint.category).into()
+            }),
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, constraint| {
+                adapter.span_to_string(ctxt, constraint.span).into()
+            }),
+        ),
+        "sub" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, constraint| {
+                format!("{:?}", constraint.sub).into()
+            }),
+        ),
+        "sup" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, constraint| {
+                format!("{:?}", constraint.sup).into()
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'RegionConstraint'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_stability_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    /// Looks up the feature, the level and, for const stability, whether the
+    /// definition is `#[rustc_promotable]`.
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl FnOnce(Symbol, StabilityLevel, Option<bool>) -> FieldValue,
+    ) -> FieldValue {
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| match vertex {
+            Vertex::StableLevel(def_id) | Vertex::UnstableLevel(def_id) => {
+                let stability = ctxt
+                    .lookup_stability(*def_id)
+                    .expect("def_id should have a Stability struct");
+                f(stability.feature, stability.level, None)
+            }
+            Vertex::ConstStableLevel(def_id) | Vertex::ConstUnstableLevel(def_id) => {
+                let stability = ctxt
+                    .lookup_const_stability(*def_id)
+                    .expect("def_id should have a ConstStability struct");
+                f(stability.feature, stability.level, Some(stability.promotable))
+            }
+            _ => unreachable!("vertex is not a Stability"),
+        })
+    }
+
+    match property_name {
+        "feature" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |feature, _, _| {
+                FieldValue::String(feature.to_string().into())
+            }),
+        ),
+        "stable" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, level, _| {
+                FieldValue::Boolean(level.is_stable())
+            }),
+        ),
+        "promotable" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, promotable| {
+                FieldValue::Boolean(promotable.expect("vertex is not a ConstStability"))
+            }),
+        ),
+        "since" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, level, _| {
+                stable_since(level).map_or(FieldValue::NULL, |version| FieldValue::String(version.to_string().into()))
+            }),
+        ),
+        "since_major" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, level, _| {
+                stable_since(level).map_or(FieldValue::NULL, |version| FieldValue::Int64(version.major.into()))
+            }),
+        ),
+        "since_minor" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, level, _| {
+                stable_since(level).map_or(FieldValue::NULL, |version| FieldValue::Int64(version.minor.into()))
+            }),
+        ),
+        "since_patch" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, level, _| {
+                stable_since(level).map_or(FieldValue::NULL, |version| FieldValue::Int64(version.patch.into()))
+            }),
+        ),
+        "since_current" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, level, _| {
+                FieldValue::Boolean(matches!(level, StabilityLevel::Stable { since: StableSince::Current, .. }))
+            }),
+        ),
+        "allowed_through_unstable_modules" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, level, _| match level {
+                StabilityLevel::Stable { allowed_through_unstable_modules, .. } => {
+                    FieldValue::Boolean(allowed_through_unstable_modules)
+                }
+                StabilityLevel::Unstable { .. } => unreachable!("stability level is not stable"),
+            }),
+        ),
+        "reason" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, level, _| match level {
+                StabilityLevel::Unstable { reason, .. } => reason
+                    .to_opt_reason()
+                    .map_or(FieldValue::NULL, |reason| FieldValue::String(reason.to_string().into())),
+                StabilityLevel::Stable { .. } => unreachable!("stability level is not unstable"),
+            }),
+        ),
+        "issue" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, level, _| match level {
+                StabilityLevel::Unstable { issue, .. } => issue
+                    .map_or(FieldValue::NULL, |issue| FieldValue::Uint64(issue.get().into())),
+                StabilityLevel::Stable { .. } => unreachable!("stability level is not unstable"),
+            }),
+        ),
+        "soft" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, level, _| match level {
+                StabilityLevel::Unstable { is_soft, .. } => FieldValue::Boolean(is_soft),
+                StabilityLevel::Stable { .. } => unreachable!("stability level is not unstable"),
+            }),
+        ),
+        "implied_by" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, level, _| match level {
+                StabilityLevel::Unstable { implied_by, .. } => implied_by
+                    .map_or(FieldValue::NULL, |feature| FieldValue::String(feature.to_string().into())),
+                StabilityLevel::Stable { .. } => unreachable!("stability level is not unstable"),
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Stability'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_static_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "eval_error" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'");
+                shared::resolve_eval_error(item_id, adapter)
+            },
+        ),
+        "docs" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'");
+                shared::resolve_docs(item_id.owner_id.to_def_id(), adapter)
+            },
+        ),
+        "ident" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                if let Some(item_id) = vertex.item_id() {
+                    shared::resolve_ident(item_id, adapter)
+                } else {
+                    unimplemented!("{vertex:?} is not an Item")
+                }
+            },
+        ),
+        "is_mut" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let item_id = vertex
+                    .as_static()
+                    .expect("vertex is not variant 'Static'");
+
+                let is_mut = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let item = ctxt.hir().item(*item_id);
+                    let ItemKind::Static(_, mutability, _) = item.kind else {
+                        unimplemented!("item was not a static: {item:#?}")
+                    };
+                    mutability.is_mut()
+                });
+
+                FieldValue::Boolean(is_mut)
+            },
+        ),
+        "effective_visibility" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_effective_visibility(def_id, adapter)
+            },
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                if let Some(item_id) = vertex.item_id() {
+                    shared::resolve_span(item_id, adapter)
+                } else {
+                    unimplemented!("{vertex:?} is not an Item")
+                }
+            },
+        ),
+        "visibility" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_visibility(def_id, adapter)
+            },
+        ),
+        "visible_in" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let def_id = vertex
+                    .item_id()
+                    .expect("vertex is not an 'Item'")
+                    .owner_id
+                    .to_def_id();
+                shared::resolve_visible_in(def_id, adapter)
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Static'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_terminator_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl FnOnce(TyCtxt<'_>, &mir::Terminator<'_>) -> FieldValue,
     ) -> FieldValue {
-        let ident: String = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+        let (origin, block) = vertex
+            .terminator()
+            .expect("vertex is not a Terminator");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+            f(ctxt, origin.body(ctxt).basic_blocks[block].terminator())
+        })
+    }
+
+    match property_name {
+        "as_string" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, terminator| {
+                format!("{:?}", terminator.kind).into()
+            }),
+        ),
+        "assert_kind" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, terminator| {
+                let mir::TerminatorKind::Assert { msg, .. } = &terminator.kind else {
+                    unreachable!("terminator is not an assert");
+                };
+                match **msg {
+                    mir::AssertKind::BoundsCheck { .. } => "bounds_check",
+                    mir::AssertKind::Overflow(..) => "overflow",
+                    mir::AssertKind::OverflowNeg(..) => "overflow_neg",
+                    mir::AssertKind::DivisionByZero(..) => "division_by_zero",
+                    mir::AssertKind::RemainderByZero(..) => "remainder_by_zero",
+                    mir::AssertKind::ResumedAfterReturn(..) => "resumed_after_return",
+                    mir::AssertKind::ResumedAfterPanic(..) => "resumed_after_panic",
+                    mir::AssertKind::MisalignedPointerDereference { .. } => "misaligned_pointer_dereference",
+                }.into()
+            }),
+        ),
+        "expected" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, terminator| {
+                let mir::TerminatorKind::Assert { expected, .. } = terminator.kind else {
+                    unreachable!("terminator is not an assert");
+                };
+                expected.into()
+            }),
+        ),
+        "kind" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, terminator| {
+                terminator.kind.name().into()
+            }),
+        ),
+        "place" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, terminator| {
+                let mir::TerminatorKind::Drop { place, .. } = terminator.kind else {
+                    unreachable!("terminator is not a drop");
+                };
+                format!("{place:?}").into()
+            }),
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, terminator| {
+                adapter.span_to_string(ctxt, terminator.source_info.span).into()
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Terminator'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_thir_arm_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let (&def_id, &arm) = vertex.as_thir_arm().expect("vertex is not a ThirArm");
+
+                adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+                    with_thir(ctxt, def_id, |thir, _| adapter.span_to_string(ctxt, thir[arm].span).into())
+                        .expect("THIR should have been kept")
+                })
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'ThirArm'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_thir_expr_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl FnOnce(TyCtxt<'_>, LocalDefId, &thir::Expr<'_>) -> FieldValue,
+    ) -> FieldValue {
+        let (def_id, expr) = vertex
+            .thir_expr()
+            .expect("vertex is not a ThirExpr");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+            with_thir(ctxt, def_id, |thir, _| f(ctxt, def_id, &thir[expr]))
+                .expect("THIR should have been kept")
+        })
+    }
+
+    match property_name {
+        "from_hir_call" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, expr| {
+                let thir::ExprKind::Call { from_hir_call, .. } = expr.kind else {
+                    unreachable!("expression is not a call");
+                };
+                from_hir_call.into()
+            }),
+        ),
+        "is_mut" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, expr| {
+                let thir::ExprKind::Borrow { borrow_kind, .. } = expr.kind else {
+                    unreachable!("expression is not a borrow");
+                };
+                matches!(borrow_kind, mir::BorrowKind::Mut { .. }).into()
+            }),
+        ),
+        "kind" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, expr| {
+                variant_name(&expr.kind).into()
+            }),
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, _, expr| {
+                adapter.span_to_string(ctxt, expr.span).into()
+            }),
+        ),
+        "temp_lifetime" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, def_id, expr| {
+                match expr.temp_lifetime {
+                    Some(scope) => {
+                        let scope_tree = ctxt.region_scope_tree(def_id.to_def_id());
+                        adapter.span_to_string(ctxt, scope.span(ctxt, scope_tree)).into()
+                    }
+                    None => FieldValue::Null,
+                }
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'ThirExpr'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_thir_pat_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl FnOnce(TyCtxt<'_>, &thir::Pat<'_>) -> FieldValue,
+    ) -> FieldValue {
+        let (&def_id, &index) = vertex
+            .as_thir_pat()
+            .expect("vertex is not a ThirPat");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+            with_thir(ctxt, def_id, |thir, body| f(ctxt, thir_pats(thir, body)[index]))
+                .expect("THIR should have been kept")
+        })
+    }
+
+    match property_name {
+        "binding" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, pat| {
+                match pat.kind {
+                    thir::PatKind::Binding { name, .. } => name.to_string().into(),
+                    _ => FieldValue::Null,
+                }
+            }),
+        ),
+        "kind" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, pat| {
+                variant_name(&pat.kind).into()
+            }),
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, pat| {
+                adapter.span_to_string(ctxt, pat.span).into()
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'ThirPat'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_thir_stmt_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl FnOnce(TyCtxt<'_>, &thir::Thir<'_>, &thir::Stmt<'_>) -> FieldValue,
+    ) -> FieldValue {
+        let (&def_id, &stmt) = vertex
+            .as_thir_stmt()
+            .expect("vertex is not a ThirStmt");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+            with_thir(ctxt, def_id, |thir, _| f(ctxt, thir, &thir[stmt]))
+                .expect("THIR should have been kept")
+        })
+    }
+
+    match property_name {
+        "kind" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |_, _, stmt| {
+                match stmt.kind {
+                    thir::StmtKind::Expr { .. } => "expr",
+                    thir::StmtKind::Let { .. } => "let",
+                }.into()
+            }),
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, thir, stmt| {
+                let span = match stmt.kind {
+                    thir::StmtKind::Expr { expr, .. } => thir[expr].span,
+                    thir::StmtKind::Let { span, .. } => span,
+                };
+                adapter.span_to_string(ctxt, span).into()
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'ThirStmt'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_ty_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "as_string" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let Vertex::Ty(origin) = vertex else {
+                    unimplemented!("vertex is not Ty: {vertex:#?}")
+                };
+                let string: String = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    origin.ty(ctxt, adapter).to_string()
+                });
+                FieldValue::String(string.into())
+            },
+        ),
+        "has_significant_drop" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let Vertex::Ty(origin) = vertex else {
+                    unimplemented!("vertex is not Ty: {vertex:#?}")
+                };
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    origin.ty(ctxt, adapter).has_significant_drop(ctxt, origin.param_env(ctxt)).into()
+                })
+            },
+        ),
+        "is_copy" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let Vertex::Ty(origin) = vertex else {
+                    unimplemented!("vertex is not Ty: {vertex:#?}")
+                };
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    origin.ty(ctxt, adapter).is_copy_modulo_regions(ctxt, origin.param_env(ctxt)).into()
+                })
+            },
+        ),
+        "needs_drop" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let Vertex::Ty(origin) = vertex else {
+                    unimplemented!("vertex is not Ty: {vertex:#?}")
+                };
+                adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    origin.ty(ctxt, adapter).needs_drop(ctxt, origin.param_env(ctxt)).into()
+                })
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Ty'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_unary_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "is_overloaded" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .as_unary()
+                    .expect("vertex is not variant 'Unary'");
+                shared::resolve_is_overloaded(*hir_id, adapter)
+            },
+        ),
+        "operator" => resolve_property_with(
+            contexts,
+            move |vertex| {
+                let hir_id = vertex
+                    .as_unary()
+                    .expect("vertex is not variant 'Unary'");
+
+                let operator = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+                    let expr = ctxt.hir().expect_expr(*hir_id);
+                    let ExprKind::Unary(op, _) = expr.kind else {
+                        unimplemented!("expr was not of type Unary: {expr:#?}")
+                    };
+                    op.as_str()
+                });
+
+                FieldValue::String(operator.into())
+            },
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Unary'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_unsafe_operation_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+    adapter: &'a Adapter,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn resolve_with(
+        vertex: &Vertex,
+        adapter: &Adapter,
+        f: impl for<'tcx> FnOnce(TyCtxt<'tcx>, &thir::Thir<'tcx>, thir::ExprId) -> FieldValue,
+    ) -> FieldValue {
+        let (&def_id, &expr) = vertex
+            .as_unsafe_operation()
+            .expect("vertex is not an UnsafeOperation");
+
+        adapter.queries.global_ctxt().unwrap().enter(|ctxt| {
+            with_thir(ctxt, def_id, |thir, _| f(ctxt, thir, expr))
+                .expect("THIR should have been kept")
+        })
+    }
+
+    match property_name {
+        "kind" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, thir, expr| {
+                unsafe_operation_kind(ctxt, thir, expr)
+                    .expect("expression doesn't require unsafe")
+                    .into()
+            }),
+        ),
+        "span" => resolve_property_with(
+            contexts,
+            move |vertex| resolve_with(vertex, adapter, |ctxt, thir, expr| {
+                adapter.span_to_string(ctxt, thir[expr].span).into()
+            }),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'UnsafeOperation'"
+            )
+        }
+    }
+}
+
+pub(crate) mod shared {
+    use rustc_hir::{HirId, ItemId};
+    use rustc_middle::mir::interpret::ErrorHandled;
+    use trustfall::FieldValue;
+
+    use rustc_span::def_id::DefId;
+
+    use rustc_hir::def::DefKind;
+    use rustc_middle::{middle::privacy::Level, ty::{TyCtxt, Visibility}};
+
+    use crate::adapter::{Adapter, util::{docs, eval_const_item}};
+
+    /// The module `def_id` is visible in, `Some(None)` if it's public,
+    /// or [`None`] if it's a kind of definition that has no visibility.
+    fn restricted_to(ctxt: TyCtxt<'_>, def_id: DefId) -> Option<Option<DefId>> {
+        let has_visibility = !matches!(
+            ctxt.def_kind(def_id),
+            DefKind::TyParam
+                | DefKind::ConstParam
+                | DefKind::LifetimeParam
+                | DefKind::AnonConst
+                | DefKind::InlineConst
+                | DefKind::OpaqueTy
+                | DefKind::Closure
+        );
+        has_visibility.then(|| match ctxt.visibility(def_id) {
+            Visibility::Public => None,
+            Visibility::Restricted(module) => Some(module),
+        })
+    }
+
+    pub fn resolve_visibility(
+        def_id: DefId,
+        adapter: &Adapter,
+    ) -> FieldValue {
+        let visibility = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+            let module = restricted_to(ctxt, def_id)?;
+            let parent_module = std::iter::successors(ctxt.opt_parent(def_id), |id| ctxt.opt_parent(*id))
+                .find(|id| ctxt.def_kind(*id) == DefKind::Mod);
+            Some(match module {
+                None => "public",
+                Some(module) if Some(module) == parent_module => "private",
+                Some(module) if module.is_crate_root() => "crate",
+                Some(_) => "restricted",
+            })
+        });
+        visibility.map_or(FieldValue::Null, |visibility| FieldValue::String(visibility.into()))
+    }
+
+    pub fn resolve_visible_in(
+        def_id: DefId,
+        adapter: &Adapter,
+    ) -> FieldValue {
+        let path = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+            let module = restricted_to(ctxt, def_id).flatten()?;
+            Some(if module.is_crate_root() {
+                "crate".to_owned()
+            } else {
+                ctxt.def_path_str(module)
+            })
+        });
+        path.map_or(FieldValue::Null, |path| FieldValue::String(path.into()))
+    }
+
+    pub fn resolve_effective_visibility(
+        def_id: DefId,
+        adapter: &Adapter,
+    ) -> FieldValue {
+        let level = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+            let effective_vis = ctxt.effective_visibilities(()).effective_vis(def_id.as_local()?)?;
+            Level::all_levels()
+                .into_iter()
+                .find(|level| effective_vis.is_public_at_level(*level))
+        });
+        let level = level.map(|level| match level {
+            Level::Direct => "direct",
+            Level::Reexported => "reexported",
+            Level::Reachable => "reachable",
+            Level::ReachableThroughImplTrait => "reachable_through_impl_trait",
+        });
+        level.map_or(FieldValue::Null, |level| FieldValue::String(level.into()))
+    }
+
+    pub fn resolve_docs(
+        def_id: DefId,
+        adapter: &Adapter,
+    ) -> FieldValue {
+        let docs = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+            docs(ctxt.get_attrs_unchecked(def_id))
+        });
+        docs.map_or(FieldValue::Null, |docs| FieldValue::String(docs.into()))
+    }
+
+    pub fn resolve_eval_error(
+        item_id: ItemId,
+        adapter: &Adapter,
+    ) -> FieldValue {
+        let error = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+            match eval_const_item(ctxt, adapter, item_id.owner_id.to_def_id()) {
+                Ok(_) => None,
+                Err(ErrorHandled::Reported(..)) => Some("evaluation failed with a compiler error"),
+                Err(ErrorHandled::TooGeneric(_)) => Some("value depends on generic parameters"),
+            }
+        });
+        error.map_or(FieldValue::Null, |error| FieldValue::String(error.into()))
+    }
+
+    pub fn resolve_is_overloaded(
+        hir_id: HirId,
+        adapter: &Adapter,
+    ) -> FieldValue {
+        let overloaded = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
             let hir = ctxt.hir();
-            hir.item(item_id).ident.as_str().into()
+            ctxt
+                .typeck(hir.enclosing_body_owner(hir_id))
+                .is_method_call(hir.expect_expr(hir_id))
+        });
+        FieldValue::Boolean(overloaded)
+    }
+
+    pub fn resolve_span(
+        item_id: ItemId,
+        adapter: &Adapter,
+    ) -> FieldValue {
+        let span = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+            adapter.span_to_string(ctxt, ctxt.def_span(item_id.owner_id))
+        });
+        FieldValue::String(span.into())
+    }
+
+    pub fn resolve_ident(
+        item_id: ItemId,
+        adapter: &Adapter,
+    ) -> FieldValue {
+        let ident: String = adapter.queries.global_ctxt().unwrap().enter(move |ctxt| {
+            ctxt.hir().name(item_id.hir_id()).as_str().into()
         });
         FieldValue::String(ident.into())
     }
@@ -2,6 +2,7 @@ use std::{collections::BTreeMap, sync::Arc};
 
 use itertools::Itertools;
 use rustc_interface::run_compiler;
+use rustc_span::edition::Edition;
 use trustfall::{provider::check_adapter_invariants, execute_query, FieldValue};
 
 use crate::compiler_config::CompilerConfig;
@@ -97,3 +98,1441 @@ fn msrv_methods() {
         })
     });
 }
+
+#[test]
+/// Find methods stabilized after Rust 1.50, e.g. to check an MSRV.
+fn stabilized_after() {
+    let config = CompilerConfig::new("main.rs", r#"fn main() {
+    let x = Some(42);
+    x.is_some();
+    x.is_some_and(|x| x > 0);
+    "a,b".split_once(',');
+}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/stabilized_after.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::from([
+                    ("major".into(), 1.into()),
+                    ("minor".into(), 50.into()),
+                    ("false".into(), false.into()),
+                ])
+            )
+                .unwrap()
+                .map(|x| (x["path"].clone(), x["since_minor"].clone()))
+                .collect_vec();
+            assert_eq!(result, vec![
+                (FieldValue::String("std::option::Option::<T>::is_some_and".into()), FieldValue::Int64(70)),
+                (FieldValue::String("core::str::<impl str>::split_once".into()), FieldValue::Int64(52)),
+            ]);
+        })
+    });
+}
+
+#[test]
+/// Find `async fn`s that hold a `MutexGuard` across an `.await`.
+fn async_saved_locals() {
+    let config = CompilerConfig::new("main.rs", r#"use std::sync::Mutex;
+async fn yield_now() {}
+async fn hold(m: &Mutex<i32>) {
+    let guard = m.lock().unwrap();
+    yield_now().await;
+    drop(guard);
+}
+fn main() {}"#).with_edition(Edition::Edition2021);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/async_saved_locals.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::from([("true".into(), true.into())])
+            )
+                .unwrap()
+                .filter(|x| x["as_string"].as_str().unwrap().contains("MutexGuard"))
+                .map(|x| x["ident"].clone())
+                .collect_vec();
+            assert_eq!(result, vec![FieldValue::String("hold".into())]);
+        })
+    });
+}
+
+#[test]
+/// Find the `Deref` impls rustc implicitly calls during autoderef.
+fn overloaded_deref_adjustments() {
+    let config = CompilerConfig::new("main.rs", r#"fn main() {
+    let s = String::from("hi");
+    s.trim();
+}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/overloaded_deref_adjustments.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .collect_vec();
+            assert_eq!(result.len(), 1);
+            let result = result.first().unwrap();
+            assert_eq!(result["ty"], FieldValue::String("std::string::String".into()));
+            assert_eq!(result["adjusted"], FieldValue::String("&str".into()));
+            assert_eq!(result["path"], FieldValue::String("std::ops::Deref::deref".into()));
+        })
+    });
+}
+
+#[test]
+/// Tell apart the binary operators that call a trait method from the builtin ones.
+fn overloaded_operators() {
+    let config = CompilerConfig::new("main.rs", r#"#[derive(Clone, Copy)]
+struct Meters(u32);
+impl std::ops::Add for Meters {
+    type Output = Meters;
+    fn add(self, rhs: Meters) -> Meters { Meters(self.0 + rhs.0) }
+}
+fn main() {
+    let _ = Meters(1) + Meters(2);
+}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/overloaded_operators.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|x| (x["operator"].clone(), x["is_overloaded"].clone(), x["path"].clone(), x["impl_path"].clone()))
+                .sorted_by_key(|(_, is_overloaded, _, _)| is_overloaded.as_bool())
+                .collect_vec();
+            assert_eq!(result, vec![
+                (FieldValue::String("+".into()), FieldValue::Boolean(false), FieldValue::Null, FieldValue::Null),
+                (
+                    FieldValue::String("+".into()),
+                    FieldValue::Boolean(true),
+                    FieldValue::String("std::ops::Add::add".into()),
+                    FieldValue::String("<Meters as std::ops::Add>::add".into()),
+                ),
+            ]);
+        })
+    });
+}
+
+#[test]
+/// Find the trait methods and impls behind overloaded unary, compound assignment and index operators.
+fn overloaded_unary_assign_and_index_operators() {
+    let config = CompilerConfig::new("main.rs", r#"#[derive(Clone, Copy)]
+struct Meters(u32);
+impl std::ops::Neg for Meters {
+    type Output = Meters;
+    fn neg(self) -> Meters { self }
+}
+impl std::ops::AddAssign for Meters {
+    fn add_assign(&mut self, rhs: Meters) { self.0 += rhs.0; }
+}
+impl std::ops::Index<usize> for Meters {
+    type Output = u32;
+    fn index(&self, _: usize) -> &u32 { &self.0 }
+}
+fn main() {
+    let mut m = Meters(1);
+    let _ = -m;
+    m += Meters(2);
+    let _ = m[0];
+    let _ = [1, 2][0];
+}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let run = |query: &str| execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|x| (x["is_overloaded"].clone(), x["path"].clone(), x["impl_path"].clone()))
+                .sorted_by_key(|(is_overloaded, _, _)| is_overloaded.as_bool())
+                .collect_vec();
+            let overloaded = |path: &str, impl_path: &str| (
+                FieldValue::Boolean(true),
+                FieldValue::String(path.into()),
+                FieldValue::String(impl_path.into()),
+            );
+            let built_in = (FieldValue::Boolean(false), FieldValue::Null, FieldValue::Null);
+
+            // The derived `Clone` dereferences `self`.
+            assert_eq!(run(include_str!("./queries/overloaded_unary.gql")), vec![
+                built_in.clone(),
+                overloaded("std::ops::Neg::neg", "<Meters as std::ops::Neg>::neg"),
+            ]);
+            assert_eq!(run(include_str!("./queries/overloaded_assign_op.gql")), vec![
+                built_in.clone(),
+                overloaded("std::ops::AddAssign::add_assign", "<Meters as std::ops::AddAssign>::add_assign"),
+            ]);
+            assert_eq!(run(include_str!("./queries/overloaded_index.gql")), vec![
+                built_in,
+                overloaded("std::ops::Index::index", "<Meters as std::ops::Index<usize>>::index"),
+            ]);
+        })
+    });
+}
+
+#[test]
+/// Find magic numbers and hard-coded strings.
+fn literals() {
+    let config = CompilerConfig::new("main.rs", r#"fn main() {
+    let _ = 1_000u64;
+    let _ = 2.5;
+    let _ = "https://example.com";
+    let _ = b'x';
+    let _ = false;
+}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/literals.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|x| (
+                    x["kind"].as_str().unwrap().to_owned(),
+                    x["text"].as_str().unwrap().to_owned(),
+                    x["ty"].as_str().unwrap().to_owned(),
+                    x,
+                ))
+                .sorted_by(|a, b| a.1.cmp(&b.1))
+                .collect_vec();
+            let summary = result
+                .iter()
+                .map(|(kind, text, ty, _)| (kind.as_str(), text.as_str(), ty.as_str()))
+                .collect_vec();
+            assert_eq!(summary, vec![
+                ("str", "\"https://example.com\"", "&str"),
+                ("int", "1_000u64", "u64"),
+                ("float", "2.5", "f64"),
+                ("byte", "b'x'", "u8"),
+                ("bool", "false", "bool"),
+            ]);
+
+            assert_eq!(result[0].3["string_value"], FieldValue::String("https://example.com".into()));
+            assert_eq!(result[1].3["suffix"], FieldValue::String("u64".into()));
+            assert_eq!(result[1].3["int_value"], FieldValue::Int64(1000));
+            assert_eq!(result[2].3["float_value"], FieldValue::Float64(2.5));
+            assert_eq!(result[3].3["int_value"], FieldValue::Int64(120));
+            assert_eq!(result[4].3["bool_value"], FieldValue::Boolean(false));
+        })
+    });
+}
+
+#[test]
+/// Read the values that `const` and `static` items evaluate to.
+fn const_values() {
+    let config = CompilerConfig::new("main.rs", r#"enum Mode { Fast }
+struct Limits { retries: u8, timeout: f64 }
+const URL: &str = "https://example.com";
+const RETRIES: i32 = -(1 << 2) + 1;
+const PAIR: (bool, char) = (true, 'x');
+const LIMITS: Limits = Limits { retries: 3, timeout: 1.5 };
+const MODE: Mode = Mode::Fast;
+static mut COUNTER: [u16; 2] = [1, 2];
+static NAME: &str = "name";
+static LIMIT: u32 = 5;
+fn main() {}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/const_values.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .sorted_by_key(|x| x["ident"].as_str().map(ToOwned::to_owned))
+                .collect_vec();
+            let [limits, mode, pair, retries, url] = &result[..] else {
+                panic!("unexpected results: {result:#?}");
+            };
+            for row in &result {
+                assert_eq!(row["eval_error"], FieldValue::Null);
+            }
+
+            assert_eq!(limits["ty"], FieldValue::String("Limits".into()));
+            assert_eq!(limits["variant"], FieldValue::Null);
+            assert_eq!(limits["field_int"], vec![FieldValue::Uint64(3), FieldValue::Null].into());
+            assert_eq!(limits["field_float"], vec![FieldValue::Null, FieldValue::Float64(1.5)].into());
+
+            assert_eq!(mode["variant"], FieldValue::String("Fast".into()));
+            assert_eq!(mode["field_int"], Vec::<FieldValue>::new().into());
+
+            assert_eq!(pair["field_bool"], vec![FieldValue::Boolean(true), FieldValue::Null].into());
+            assert_eq!(pair["field_string"], vec![FieldValue::Null, FieldValue::String("x".into())].into());
+
+            assert_eq!(retries["ty"], FieldValue::String("i32".into()));
+            assert_eq!(retries["int_value"], FieldValue::Int64(-3));
+
+            assert_eq!(url["ty"], FieldValue::String("&'static str".into()));
+            assert_eq!(url["string_value"], FieldValue::String("https://example.com".into()));
+
+            let query = include_str!("./queries/static_values.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|x| (
+                    x["ident"].clone(),
+                    x["is_mut"].clone(),
+                    x["int_value"].clone(),
+                    x["string_value"].clone(),
+                    x["field_int"].clone(),
+                ))
+                .sorted_by_key(|x| x.0.as_str().map(ToOwned::to_owned))
+                .collect_vec();
+            assert_eq!(result, vec![
+                (
+                    FieldValue::String("COUNTER".into()),
+                    FieldValue::Boolean(true),
+                    FieldValue::Null,
+                    FieldValue::Null,
+                    vec![FieldValue::Uint64(1), FieldValue::Uint64(2)].into(),
+                ),
+                (
+                    FieldValue::String("LIMIT".into()),
+                    FieldValue::Boolean(false),
+                    FieldValue::Uint64(5),
+                    FieldValue::Null,
+                    Vec::<FieldValue>::new().into(),
+                ),
+                (
+                    FieldValue::String("NAME".into()),
+                    FieldValue::Boolean(false),
+                    FieldValue::Null,
+                    FieldValue::String("name".into()),
+                    Vec::<FieldValue>::new().into(),
+                ),
+            ]);
+        })
+    });
+}
+
+#[test]
+/// Read statics that hold pointers, and find constants that fail to evaluate.
+fn pointer_statics_and_eval_errors() {
+    let config = CompilerConfig::new("main.rs", r#"static ONE: i32 = 1;
+static SOME: Option<&i32> = Some(&ONE);
+static NONE: Option<&i32> = None;
+const BAD: u8 = [1][1];
+const GOOD: u8 = 1;
+fn main() {}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/pointer_statics.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|x| (x["ident"].clone(), x["int_value"].clone(), x["variant"].clone(), x["field_int"].clone()))
+                .sorted_by_key(|x| x.0.as_str().map(ToOwned::to_owned))
+                .collect_vec();
+            assert_eq!(result, vec![
+                (FieldValue::String("NONE".into()), FieldValue::Null, FieldValue::String("None".into()), Vec::<FieldValue>::new().into()),
+                (FieldValue::String("ONE".into()), FieldValue::Int64(1), FieldValue::Null, Vec::<FieldValue>::new().into()),
+                (FieldValue::String("SOME".into()), FieldValue::Null, FieldValue::String("Some".into()), vec![FieldValue::Null].into()),
+            ]);
+
+            let query = include_str!("./queries/const_eval_errors.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|x| (x["ident"].clone(), x["eval_error"].clone(), x["value_count"].clone()))
+                .sorted_by_key(|x| x.0.as_str().map(ToOwned::to_owned))
+                .collect_vec();
+            assert_eq!(result, vec![
+                (
+                    FieldValue::String("BAD".into()),
+                    FieldValue::String("evaluation failed with a compiler error".into()),
+                    FieldValue::Uint64(0),
+                ),
+                (FieldValue::String("GOOD".into()), FieldValue::Null, FieldValue::Uint64(1)),
+            ]);
+        })
+    });
+}
+
+#[test]
+/// Find lint suppressions and doc comments on items.
+fn item_attributes() {
+    let config = CompilerConfig::new("main.rs", r#"/// Adds one.
+#[must_use = "pure function"]
+#[allow(unused_variables, clippy::all)]
+fn add_one(x: i32) -> i32 { x + 1 }
+fn main() {}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/item_attributes.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .filter(|x| x["ident"] == FieldValue::String("add_one".into()))
+                .collect_vec();
+            let [doc, must_use, allow] = &result[..] else {
+                panic!("unexpected results: {result:#?}");
+            };
+
+            assert_eq!(doc["path"], FieldValue::String("doc".into()));
+            assert_eq!(doc["is_doc"], FieldValue::Boolean(true));
+            assert_eq!(doc["style"], FieldValue::String("outer".into()));
+            assert_eq!(doc["value_str"], FieldValue::String(" Adds one.".into()));
+            assert_eq!(doc["tokens"], FieldValue::Null);
+
+            assert_eq!(must_use["path"], FieldValue::String("must_use".into()));
+            assert_eq!(must_use["is_doc"], FieldValue::Boolean(false));
+            assert_eq!(must_use["value_str"], FieldValue::String("pure function".into()));
+            assert_eq!(must_use["tokens"], FieldValue::String("\"pure function\"".into()));
+
+            assert_eq!(allow["path"], FieldValue::String("allow".into()));
+            assert_eq!(allow["value_str"], FieldValue::Null);
+            assert_eq!(allow["tokens"], FieldValue::String("unused_variables, clippy::all".into()));
+            assert_eq!(allow["meta_item_path"], vec![
+                FieldValue::String("unused_variables".into()),
+                FieldValue::String("clippy::all".into()),
+            ].into());
+        })
+    });
+}
+
+#[test]
+/// Read the attributes of methods defined in other crates.
+fn must_use_methods() {
+    let config = CompilerConfig::new("main.rs", r#"fn main() {
+    let x = Some(1);
+    x.is_some();
+}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/must_use_methods.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::from([("attribute".into(), "must_use".into())])
+            )
+                .unwrap()
+                .collect_vec();
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0]["path"], FieldValue::String("std::option::Option::<T>::is_some".into()));
+            assert!(result[0]["value_str"].as_str().is_some());
+        })
+    });
+}
+
+#[test]
+/// Tell the method a call resolves to from the ADT of the type it returns.
+fn ty_def_and_adt() {
+    let config = CompilerConfig::new("main.rs", r#"fn main() {
+    let x = Some(1);
+    let _ = x.map(|x| x + 1);
+}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/ty_def_and_adt.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .collect_vec();
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0]["method"], FieldValue::String("std::option::Option::<T>::map".into()));
+            assert_eq!(result[0]["adt"], FieldValue::String("std::option::Option".into()));
+        })
+    });
+}
+
+#[test]
+/// Find undocumented functions and the items their docs link to.
+fn doc_links() {
+    let config = CompilerConfig::new("main.rs", r#"mod inner {
+    pub struct Hidden;
+}
+pub struct Widget;
+/// Creates a [`Widget`].
+///
+/// See [`inner::Hidden`], [Missing] and [the book](https://example.com).
+pub fn make() {}
+pub fn undocumented() {}
+fn main() {}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/doc_links.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .sorted_by_key(|x| x["ident"].as_str().map(ToOwned::to_owned))
+                .collect_vec();
+            let [main, make, undocumented] = &result[..] else {
+                panic!("unexpected results: {result:#?}");
+            };
+
+            assert_eq!(main["docs"], FieldValue::Null);
+            assert_eq!(undocumented["docs"], FieldValue::Null);
+            assert_eq!(
+                make["docs"],
+                FieldValue::String("Creates a [`Widget`].\n\nSee [`inner::Hidden`], [Missing] and [the book](https://example.com).".into()),
+            );
+            assert_eq!(make["link"], vec![
+                FieldValue::String("Widget".into()),
+                FieldValue::String("inner::Hidden".into()),
+                FieldValue::String("Missing".into()),
+            ].into());
+            assert_eq!(make["is_resolved"], vec![
+                FieldValue::Boolean(true),
+                FieldValue::Boolean(true),
+                FieldValue::Boolean(false),
+            ].into());
+            assert_eq!(make["path"], vec![
+                FieldValue::String("Widget".into()),
+                FieldValue::String("inner::Hidden".into()),
+                FieldValue::Null,
+            ].into());
+        })
+    });
+}
+
+#[test]
+/// Resolve the links in the outer and inner docs of modules.
+fn module_doc_links() {
+    let config = CompilerConfig::new("main.rs", r#"/// See [`Widget`].
+mod inner {}
+mod other {
+    //! Home of [`Helper`].
+    pub struct Helper;
+}
+pub struct Widget;
+fn main() {}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/module_doc_links.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::from([("module".into(), "module".into())])
+            )
+                .unwrap()
+                .map(|x| (x["path_segment"].clone(), x["link"].clone(), x["is_resolved"].clone()))
+                .collect_vec();
+            assert_eq!(result, vec![
+                (vec!["rust_out", "inner"].into(), FieldValue::String("Widget".into()), FieldValue::Boolean(true)),
+                (vec!["rust_out", "other"].into(), FieldValue::String("Helper".into()), FieldValue::Boolean(true)),
+            ]);
+        })
+    });
+}
+
+#[test]
+/// Tell apart declared and effective visibility, e.g. to find `pub` items nothing exports.
+fn visibility() {
+    let config = CompilerConfig::new("lib.rs", r#"pub mod api {
+    pub fn exported() {}
+    pub(crate) fn internal() {}
+    fn private() {}
+    pub mod v1 {
+        pub(super) fn parent_only() {}
+    }
+}
+mod hidden {
+    pub fn unreachable() {}
+    pub struct Leaked;
+    struct Secret {
+        pub key: u32,
+        salt: u32,
+    }
+}
+pub use hidden::Leaked;
+"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/visibility.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|x| (
+                    x["ident"].as_str().unwrap().to_owned(),
+                    x["visibility"].as_str().unwrap().to_owned(),
+                    x["visible_in"].as_str().map(ToOwned::to_owned),
+                    x["effective_visibility"].as_str().map(ToOwned::to_owned),
+                ))
+                .filter(|(ident, ..)| !ident.is_empty() && ident != "std")
+                .sorted()
+                .collect_vec();
+            let expected: Vec<(&str, &str, Option<&str>, Option<&str>)> = vec![
+                ("Leaked", "public", None, Some("direct")),
+                ("Leaked", "public", None, Some("reexported")),
+                ("Secret", "private", Some("hidden"), None),
+                ("api", "public", None, Some("direct")),
+                ("exported", "public", None, Some("direct")),
+                ("hidden", "private", Some("crate"), None),
+                ("internal", "crate", Some("crate"), None),
+                ("parent_only", "restricted", Some("api"), None),
+                ("private", "private", Some("api"), None),
+                ("unreachable", "public", None, None),
+                ("v1", "public", None, Some("direct")),
+            ];
+            assert_eq!(
+                result,
+                expected
+                    .into_iter()
+                    .map(|(a, b, c, d)| (a.to_owned(), b.to_owned(), c.map(ToOwned::to_owned), d.map(ToOwned::to_owned)))
+                    .collect_vec(),
+            );
+
+            // A `pub` field of a private struct isn't reachable from other crates.
+            let query = include_str!("./queries/field_visibility.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|x| (
+                    x["field"].as_vec_with(FieldValue::as_str).unwrap().join("::"),
+                    x["kind"].as_str().unwrap().to_owned(),
+                    x["visibility"].as_str().unwrap().to_owned(),
+                    x["effective_visibility"].as_str().map(ToOwned::to_owned),
+                ))
+                .sorted()
+                .collect_vec();
+            let expected: Vec<(&str, &str, &str, Option<&str>)> = vec![
+                ("rust_out::hidden::Secret::key", "field", "public", None),
+                ("rust_out::hidden::Secret::salt", "field", "private", None),
+            ];
+            assert_eq!(
+                result,
+                expected
+                    .into_iter()
+                    .map(|(a, b, c, d)| (a.to_owned(), b.to_owned(), c.to_owned(), d.map(ToOwned::to_owned)))
+                    .collect_vec(),
+            );
+        })
+    });
+}
+
+#[test]
+/// Find calls into deprecated APIs, both in std and in the crate itself.
+fn deprecated_calls() {
+    let config = CompilerConfig::new("main.rs", r#"#![allow(deprecated)]
+struct S;
+impl S {
+    #[deprecated(since = "0.2.0", note = "use `new` instead")]
+    fn old(&self) {}
+    fn new(&self) {}
+}
+fn main() {
+    " hi".trim_left();
+    S.old();
+    S.new();
+}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/deprecated_calls.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|x| (
+                    x["path"].clone(),
+                    x["since"].clone(),
+                    x["is_in_effect"].clone(),
+                    x["is_rustc_deprecation"].clone(),
+                ))
+                .collect_vec();
+            assert_eq!(result, vec![
+                (
+                    FieldValue::String("core::str::<impl str>::trim_left".into()),
+                    FieldValue::String("1.33.0".into()),
+                    FieldValue::Boolean(true),
+                    FieldValue::Boolean(true),
+                ),
+                (
+                    FieldValue::String("S::old".into()),
+                    FieldValue::String("0.2.0".into()),
+                    FieldValue::Boolean(true),
+                    FieldValue::Boolean(false),
+                ),
+            ]);
+        })
+    });
+}
+
+#[test]
+/// Find the feature gates a crate enables, and which of them are stable by now.
+fn crate_features() {
+    let config = CompilerConfig::new("main.rs", r#"#![allow(stable_features)]
+#![feature(let_chains, associated_consts)]
+#![feature(iter_intersperse, is_some_and)]
+fn main() {}"#).with_edition(Edition::Edition2021);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/crate_features.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::from([("cfg".into(), "debug_assertions".into())])
+            )
+                .unwrap()
+                .collect_vec();
+            assert!(result.iter().all(|row| {
+                row["name"] == FieldValue::String("rust_out".into())
+                    && row["edition"] == FieldValue::String("2021".into())
+                    && row["crate_type"] == vec!["bin"].into()
+            }));
+            let features = result
+                .iter()
+                .map(|row| (row["feature"].clone(), row["kind"].clone(), row["since"].clone()))
+                .collect_vec();
+            assert_eq!(features, vec![
+                (FieldValue::String("let_chains".into()), FieldValue::String("lang".into()), FieldValue::Null),
+                (FieldValue::String("associated_consts".into()), FieldValue::String("lang".into()), FieldValue::String("1.20.0".into())),
+                (FieldValue::String("iter_intersperse".into()), FieldValue::String("lib".into()), FieldValue::Null),
+                (FieldValue::String("is_some_and".into()), FieldValue::String("lib".into()), FieldValue::String("1.70.0".into())),
+            ]);
+            assert_eq!(result[2]["span"], FieldValue::String("<main.rs>:3:12: 3:28".into()));
+        })
+    });
+}
+
+#[test]
+/// Find where definitions used by the crate come from among its dependencies.
+fn dependencies() {
+    let config = CompilerConfig::new("main.rs", "fn main() {}");
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/dependencies.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::from([
+                    ("crates".into(), vec!["std", "core", "alloc"].into()),
+                    ("paths".into(), vec!["std::option::Option", "std::vec::Vec"].into()),
+                ])
+            )
+                .unwrap()
+                .map(|row| (row["name"].clone(), row["dep_kind"].clone(), row["exported"].clone()))
+                .sorted_by_key(|(name, _, exported)| (name.as_str().unwrap().to_owned(), exported.as_str().unwrap().to_owned()))
+                .dedup()
+                .collect_vec();
+            assert_eq!(result, vec![
+                (FieldValue::String("alloc".into()), FieldValue::String("explicit".into()), FieldValue::String("std::vec::Vec".into())),
+                (FieldValue::String("core".into()), FieldValue::String("explicit".into()), FieldValue::String("std::option::Option".into())),
+                (FieldValue::String("std".into()), FieldValue::String("explicit".into()), FieldValue::String("std::option::Option".into())),
+                (FieldValue::String("std".into()), FieldValue::String("explicit".into()), FieldValue::String("std::vec::Vec".into())),
+            ]);
+        })
+    });
+}
+
+#[test]
+fn defs_by_path() {
+    let config = CompilerConfig::new("main.rs", r#"mod inner {
+    pub fn helper() {}
+}
+pub use inner::helper as reexported;
+fn main() {}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                include_str!("./queries/def_by_path.gql"),
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|row| row["path"].clone())
+                .collect_vec();
+            assert_eq!(result, vec![FieldValue::String("std::vec::Vec::<T>::new".into())]);
+
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                include_str!("./queries/fn_by_path.gql"),
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|row| row["ident"].clone())
+                .collect_vec();
+            assert_eq!(result, vec![FieldValue::String("helper".into())]);
+        })
+    });
+}
+
+#[test]
+fn lang_and_diagnostic_items() {
+    let config = CompilerConfig::new("main.rs", "fn main() {}");
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                include_str!("./queries/lang_item.gql"),
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|row| row["path"].clone())
+                .collect_vec();
+            assert_eq!(result, vec![FieldValue::String("std::ops::Drop".into())]);
+
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                include_str!("./queries/diagnostic_item.gql"),
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|row| row["path"].clone())
+                .collect_vec();
+            assert_eq!(result, vec![FieldValue::String("std::vec::Vec".into())]);
+        })
+    });
+}
+
+#[test]
+fn nodes_at_position() {
+    let config = CompilerConfig::new("main.rs", r#"fn main() {
+    let x = 1 + 2;
+}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/node_at.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|row| row["__typename"].clone())
+                .collect_vec();
+            assert_eq!(result, vec![
+                FieldValue::String("Lit".into()),
+                FieldValue::String("Binary".into()),
+                FieldValue::String("LocalStatement".into()),
+                FieldValue::String("Block".into()),
+                FieldValue::String("Fn".into()),
+            ]);
+        })
+    });
+}
+
+#[test]
+fn associated_fns() {
+    let config = CompilerConfig::new("main.rs", r#"struct Counter(u32);
+impl Counter {
+    unsafe fn bump(&mut self) {
+        self.0 += 1;
+    }
+}
+fn main() {}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                include_str!("./queries/fn_by_path_assoc.gql"),
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|row| (row["ident"].clone(), row["is_unsafe"].clone(), row["parent"].clone(), row["statements"].clone()))
+                .collect_vec();
+            assert_eq!(result, vec![(
+                FieldValue::String("bump".into()),
+                FieldValue::Boolean(true),
+                FieldValue::String("Node".into()),
+                FieldValue::String("Block".into()),
+            )]);
+
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                include_str!("./queries/node_at_impl_fn.gql"),
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|row| row["__typename"].clone())
+                .collect_vec();
+            assert_eq!(result, vec![
+                FieldValue::String("Lit".into()),
+                FieldValue::String("AssignOp".into()),
+                FieldValue::String("Statement".into()),
+                FieldValue::String("Block".into()),
+                FieldValue::String("Fn".into()),
+                FieldValue::String("Impl".into()),
+            ]);
+        })
+    });
+}
+
+#[test]
+fn def_navigation() {
+    let config = CompilerConfig::new("main.rs", r#"mod inner {
+    pub fn helper() {}
+    pub struct Wrapper;
+    impl Wrapper {}
+    pub use std::vec::Vec;
+}
+fn main() {}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/def_navigation.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|row| {
+                    assert_eq!(row["crate_name"], FieldValue::String("rust_out".into()));
+                    assert_eq!(row["is_local"], FieldValue::Boolean(true));
+                    (row["kind"].clone(), row["path_segment"].clone(), row["span"].clone(), row["item"].clone())
+                })
+                .collect_vec();
+            assert_eq!(result, vec![
+                (
+                    FieldValue::String("function".into()),
+                    vec!["rust_out", "inner", "helper"].into(),
+                    FieldValue::String("<main.rs>:2:5: 2:20".into()),
+                    FieldValue::String("Fn".into()),
+                ),
+                (
+                    FieldValue::String("struct".into()),
+                    vec!["rust_out", "inner", "Wrapper"].into(),
+                    FieldValue::String("<main.rs>:3:5: 3:23".into()),
+                    FieldValue::String("Item".into()),
+                ),
+            ]);
+        })
+    });
+}
+
+#[test]
+fn lang_and_diagnostic_item_names() {
+    let config = CompilerConfig::new("main.rs", "fn main() {}");
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/lang_and_diagnostic_item_names.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::from([
+                    ("lang_items".into(), vec!["Option", "drop", "String"].into()),
+                ])
+            )
+                .unwrap()
+                .map(|row| (row["crate"].clone(), row["lang_item"].clone(), row["diagnostic_item"].clone(), row["path"].clone()))
+                .collect_vec();
+            assert_eq!(result, vec![
+                (
+                    FieldValue::String("core".into()),
+                    FieldValue::String("drop".into()),
+                    FieldValue::NULL,
+                    FieldValue::String("std::ops::Drop".into()),
+                ),
+                (
+                    FieldValue::String("core".into()),
+                    FieldValue::String("Option".into()),
+                    FieldValue::String("Option".into()),
+                    FieldValue::String("std::option::Option".into()),
+                ),
+                (
+                    FieldValue::String("alloc".into()),
+                    FieldValue::String("String".into()),
+                    FieldValue::NULL,
+                    FieldValue::String("std::string::String".into()),
+                ),
+            ]);
+        })
+    });
+}
+
+#[test]
+fn mir_locals_and_asserts() {
+    let config = CompilerConfig::new("main.rs", r#"fn get(values: &[u32], index: usize) -> u32 {
+    let value = values[index];
+    value
+}
+fn main() {}"#).with_thir();
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/mir_locals_and_asserts.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|row| (row["name"].clone(), row["kind"].clone(), row["type"].clone(), row["assert_kind"].clone()))
+                .collect_vec();
+            let assert_kinds = FieldValue::List(vec![FieldValue::String("bounds_check".into())].into());
+            assert_eq!(result, vec![
+                (FieldValue::String("values".into()), FieldValue::String("arg".into()), FieldValue::String("&[u32]".into()), assert_kinds.clone()),
+                (FieldValue::String("index".into()), FieldValue::String("arg".into()), FieldValue::String("usize".into()), assert_kinds.clone()),
+                (FieldValue::String("value".into()), FieldValue::String("user".into()), FieldValue::String("u32".into()), assert_kinds),
+            ]);
+        })
+    });
+}
+
+#[test]
+fn mir_calls_and_drops() {
+    let config = CompilerConfig::new("main.rs", r#"fn main() {
+    let values: Vec<u8> = Vec::new();
+    let copy = values.clone();
+    drop(copy);
+}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/mir_calls_and_drops.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|row| (row["block"].clone(), row["callee"].clone(), row["dropped"].clone()))
+                .collect_vec();
+            // `values` is dropped on return, and on the cleanup path of the calls after it.
+            let dropped = FieldValue::List(vec![FieldValue::String("std::vec::Vec<u8>".into()); 2].into());
+            assert_eq!(result, vec![
+                (FieldValue::Uint64(0), FieldValue::String("std::vec::Vec::<T>::new".into()), dropped.clone()),
+                (FieldValue::Uint64(1), FieldValue::String("<std::vec::Vec<T, A> as std::clone::Clone>::clone".into()), dropped.clone()),
+                (FieldValue::Uint64(2), FieldValue::String("std::mem::drop".into()), dropped),
+            ]);
+        })
+    });
+}
+
+#[test]
+fn thir_match_scrutinee_temporaries() {
+    let config = CompilerConfig::new("main.rs", r#"fn main() {
+    let lock = std::sync::Mutex::new(1u8);
+    match *lock.lock().unwrap() {
+        0 => {}
+        n => drop(n),
+    }
+}"#).with_thir();
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/thir_match_scrutinee_temporaries.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .collect_vec();
+            // The match is the tail expression of `main`, so the guard lives until `main` returns.
+            let temporaries = result
+                .iter()
+                .map(|row| (row["callee"].clone(), row["type"].clone(), row["temp_lifetime"].clone()))
+                .collect_vec();
+            let scope = FieldValue::String("<main.rs>:1:11: 7:2".into());
+            assert_eq!(temporaries, vec![
+                (
+                    FieldValue::String("<std::sync::MutexGuard<'_, T> as std::ops::Deref>::deref".into()),
+                    FieldValue::String("&u8".into()),
+                    scope.clone(),
+                ),
+                (
+                    FieldValue::String("std::result::Result::<T, E>::unwrap".into()),
+                    FieldValue::String("std::sync::MutexGuard<'_, u8>".into()),
+                    scope.clone(),
+                ),
+                (
+                    FieldValue::String("std::sync::Mutex::<T>::lock".into()),
+                    FieldValue::String("std::result::Result<std::sync::MutexGuard<'_, u8>, std::sync::PoisonError<std::sync::MutexGuard<'_, u8>>>".into()),
+                    scope,
+                ),
+            ]);
+            assert_eq!(result[0]["pattern"], vec!["Constant", "Binding"].into());
+            assert_eq!(result[0]["binding"], FieldValue::List(vec![FieldValue::Null, FieldValue::String("n".into())].into()));
+        })
+    });
+}
+
+#[test]
+fn panic_sites() {
+    let config = CompilerConfig::new("main.rs", r#"fn checked(values: &[u8], i: usize) -> u8 {
+    values[i]
+}
+
+fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+fn first(values: &Vec<u8>) -> u8 {
+    values[0]
+}
+
+fn parse(value: &str) -> u8 {
+    value.parse().unwrap()
+}
+
+fn fail() -> ! {
+    unreachable!()
+}
+
+fn calls_fail() {
+    fail()
+}
+
+fn wrapping(a: u8) -> u8 {
+    a.wrapping_add(1)
+}
+
+fn main() {
+    wrapping(1);
+}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/panic_sites.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|row| (row["ident"].clone(), row["may_panic"].clone(), row["kind"].clone()))
+                .collect_vec();
+            let expected = [
+                ("checked", true, vec!["assert"]),
+                ("add", true, vec!["assert"]),
+                ("first", true, vec!["index"]),
+                ("parse", true, vec!["track_caller"]),
+                ("fail", true, vec!["panic"]),
+                ("calls_fail", true, vec!["call"]),
+                ("wrapping", false, vec![]),
+                ("main", false, vec![]),
+            ]
+                .into_iter()
+                .map(|(ident, may_panic, kinds)| (ident.into(), may_panic.into(), kinds.into()))
+                .collect_vec();
+            assert_eq!(result, expected);
+        })
+    });
+}
+
+#[test]
+fn unsafe_inventory() {
+    let config = CompilerConfig::new("main.rs", r#"union Bits {
+    int: u32,
+    float: f32,
+}
+
+static mut COUNTER: u32 = 0;
+
+unsafe fn danger() -> u32 {
+    1
+}
+
+struct Ptr(*const u8);
+
+unsafe impl Send for Ptr {}
+
+fn main() {
+    let x = 5u32;
+    let p = &x as *const u32;
+    let mut bits = Bits { int: 1 };
+    bits.int = 2;
+    unsafe {
+        let _ = *p;
+        COUNTER += danger();
+        let _ = bits.float;
+        let read = || *p;
+        unsafe { danger(); }
+    }
+}"#).with_thir();
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/unsafe_inventory.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|row| (row["unsafe_impl"].clone(), row["is_unsafe"].clone(), row["kind"].clone(), row["callee"].clone()))
+                .collect_vec();
+            // The nested block owns the call in it, and the read in the closure belongs to the outer block.
+            assert_eq!(result, vec![
+                (
+                    vec![true].into(),
+                    true.into(),
+                    vec![
+                        "deref_of_raw_pointer",
+                        "use_of_mutable_static",
+                        "call_to_unsafe_function",
+                        "access_to_union_field",
+                        "deref_of_raw_pointer",
+                    ].into(),
+                    FieldValue::List(vec![
+                        FieldValue::Null,
+                        FieldValue::Null,
+                        FieldValue::String("danger".into()),
+                        FieldValue::Null,
+                        FieldValue::Null,
+                    ].into()),
+                ),
+                (
+                    vec![true].into(),
+                    true.into(),
+                    vec!["call_to_unsafe_function"].into(),
+                    vec!["danger"].into(),
+                ),
+            ]);
+        })
+    });
+}
+
+#[test]
+fn borrowck() {
+    let config = CompilerConfig::new("main.rs", r#"fn main() {
+    let mut v = Vec::new();
+    v.push(v.len());
+    let first = &v[0];
+    let n = *first;
+    drop(v);
+    let _ = n;
+}"#).with_borrowck_facts();
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/borrowck.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .collect_vec();
+            assert_eq!(result.len(), 1);
+            let row = &result[0];
+            // `v.push(v.len())` takes a two-phase `&mut v` that's activated by the call, after `&v` is taken
+            // and released by `v.len()`.
+            assert_eq!(row["kind"], vec!["mut", "shared", "shared"].into());
+            assert_eq!(row["is_two_phase"], vec![true, false, false].into());
+            assert_eq!(row["activation_span"], FieldValue::List(vec![
+                FieldValue::String("<main.rs>:3:5: 3:20".into()),
+                FieldValue::Null,
+                FieldValue::Null,
+            ].into()));
+            assert_eq!(row["borrowed_place"], vec!["_1", "_1", "_1"].into());
+            assert_eq!(row["local_name"], vec!["v", "v", "v"].into());
+            let FieldValue::List(categories) = &row["category"] else {
+                unreachable!("expected a list of categories")
+            };
+            assert!(categories.contains(&"Assignment".into()));
+            assert!(categories.contains(&"CallArgument".into()));
+            assert_eq!(row["moved_place"], vec!["_1"].into());
+            assert_eq!(row["moved_local"], vec!["v"].into());
+        })
+    });
+}
+
+#[test]
+fn mono_items() {
+    let config = CompilerConfig::new("main.rs", r#"fn id<T>(value: T) -> T {
+    value
+}
+
+fn main() {
+    id(1u8);
+    id("one");
+    id(2u8);
+}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/mono_items.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::from([
+                    ("kind".into(), "Item".into()),
+                    ("path".into(), "id".into()),
+                ])
+            )
+                .unwrap()
+                .collect_vec();
+            // `id::<u8>` is instantiated once, however often it's called.
+            assert_eq!(
+                result.iter().map(|row| (row["as_string"].clone(), row["ty"].clone(), row["linkage"].clone())).collect_vec(),
+                vec![
+                    ("fn id::<u8>".into(), vec!["u8"].into(), "Internal".into()),
+                    ("fn id::<&str>".into(), vec!["&str"].into(), "Internal".into()),
+                ],
+            );
+            for row in &result {
+                assert!(matches!(&row["symbol_name"], FieldValue::String(name) if name.starts_with("_ZN8rust_out2id17h")));
+                assert!(matches!(&row["codegen_unit"], FieldValue::String(name) if name.starts_with("rust_out.")));
+                assert!(matches!(row["size_estimate"], FieldValue::Uint64(size) if size > 0));
+            }
+        })
+    });
+}
+
+#[test]
+fn drops() {
+    let config = CompilerConfig::new("main.rs", r#"struct Noisy(u8);
+
+impl Drop for Noisy {
+    fn drop(&mut self) {}
+}
+
+fn main() {
+    let first = Noisy(1);
+    let second = Noisy(2);
+    {
+        let inner = Noisy(3);
+    }
+    let text = String::new();
+    let number = 5;
+}"#);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let adapter = Adapter::new(queries);
+            let schema = Adapter::schema();
+            let query = include_str!("./queries/drops.gql");
+            let result = execute_query(
+                schema,
+                (&adapter).into(),
+                query,
+                BTreeMap::<Arc<str>, FieldValue>::new()
+            )
+                .unwrap()
+                .map(|row| (
+                    row["name"].clone(),
+                    row["drop_span"].clone(),
+                    row["drop_order"].clone(),
+                    row["needs_drop"].clone(),
+                    row["has_significant_drop"].clone(),
+                    row["drop_impl"].clone(),
+                ))
+                .collect_vec();
+            // `number` is optimized into a constant, and `String` only frees memory when it's dropped.
+            assert_eq!(result, vec![
+                ("first".into(), "<main.rs>:15:1: 15:2".into(), 2u64.into(), true.into(), true.into(), "<Noisy as std::ops::Drop>".into()),
+                ("second".into(), "<main.rs>:15:1: 15:2".into(), 1u64.into(), true.into(), true.into(), "<Noisy as std::ops::Drop>".into()),
+                ("inner".into(), "<main.rs>:12:5: 12:6".into(), 0u64.into(), true.into(), true.into(), "<Noisy as std::ops::Drop>".into()),
+                ("text".into(), "<main.rs>:15:1: 15:2".into(), 0u64.into(), true.into(), false.into(), FieldValue::Null),
+            ]);
+        })
+    });
+}
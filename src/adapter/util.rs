@@ -1,8 +1,697 @@
-use trustfall::Schema;
+use rustc_ast::{Attribute, NestedMetaItem};
+use pulldown_cmark::{BrokenLink, CowStr, Event, Parser, Tag};
+use rustc_hir::{def::{DefKind, Namespace, Res}, BlockCheckMode, HirId, Mutability, UnsafeSource, Unsafety};
+use itertools::Itertools;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_middle::{
+    metadata::ModChild,
+    middle::{codegen_fn_attrs::CodegenFnAttrFlags, lib_features::FeatureStability},
+    mir::{
+        self, BasicBlock, ConstValue, DestructuredConstant, Local, Location, Operand, Place, TerminatorKind,
+        interpret::{alloc_range, ErrorHandled}, mono::{MonoItem, MonoItemData},
+    },
+    thir::{self, visit::{walk_expr, walk_pat, Visitor}, BlockSafety, ExprId, Thir},
+    ty::{self, Instance, Ty, TyCtxt, TypeVisitableExt},
+};
+use rustc_resolve::rustdoc;
+use rustc_span::{def_id::{CrateNum, DefId, LocalDefId, LOCAL_CRATE}, sym, Span, Symbol};
+use rustc_target::abi::Size;
+use trustfall::{FieldValue, Schema};
+
+use crate::query_overrides::with_thir;
+
+use super::{vertex::PanicKind, Adapter};
 
 pub(super) fn is_subtype(schema: &Schema, super_: &str, sub: &str) -> bool {
     schema
         .subtypes(super_)
-        .expect(format!("{super_:?} isn't a type in this schema").as_str())
+        .unwrap_or_else(|| panic!("{super_:?} isn't a type in this schema"))
         .any(|ty| ty == sub)
 }
+
+/// Renders `span` as `file:line:col: line:col`, the way rustc points at code in diagnostics.
+pub(super) fn span_to_string(tcx: TyCtxt<'_>, span: Span) -> String {
+    tcx.sess.source_map().span_to_diagnostic_string(span)
+}
+
+/// The crates the local crate depends on, directly or not.
+///
+/// Crates are loaded during name resolution, which [`TyCtxt::crates`] doesn't wait for,
+/// and the list it returns can't change once it's been queried. Lowering to HIR makes
+/// sure resolution has happened.
+pub(super) fn crates(tcx: TyCtxt<'_>) -> &[CrateNum] {
+    let _ = tcx.hir_crate(());
+    tcx.crates(())
+}
+
+/// The items a module, enum or trait makes nameable under it, re-exports included.
+///
+/// [`TyCtxt::module_children`] only knows about other crates, local ones are
+/// taken from the results of name resolution.
+pub(super) fn module_children(tcx: TyCtxt<'_>, def_id: DefId) -> &[ModChild] {
+    match def_id.as_local() {
+        Some(def_id) => {
+            let _ = tcx.hir_crate(());
+            tcx.module_children_local(def_id)
+        }
+        None => tcx.module_children(def_id),
+    }
+}
+
+/// Resolves a `::`-separated path such as `std::vec::Vec::new` or `crate::foo` to the
+/// definitions it names, looking through modules, enums, traits and inherent impls.
+///
+/// Several definitions can share a path if they live in different namespaces.
+pub(super) fn def_path_res(tcx: TyCtxt<'_>, path: &str) -> Vec<DefId> {
+    let mut segments = path.split("::").map(Symbol::intern);
+    let Some(first) = segments.next() else {
+        return vec![];
+    };
+    let mut defs = std::iter::once(LOCAL_CRATE)
+        .chain(crates(tcx).iter().copied())
+        .filter(|&cnum| {
+            (cnum == LOCAL_CRATE && first == rustc_span::symbol::kw::Crate)
+                || tcx.crate_name(cnum) == first
+        })
+        .map(|cnum| cnum.as_def_id())
+        .collect_vec();
+
+    for segment in segments {
+        defs = defs
+            .into_iter()
+            .flat_map(|def_id| -> Vec<DefId> {
+                match tcx.def_kind(def_id) {
+                    DefKind::Mod | DefKind::Enum | DefKind::Trait => module_children(tcx, def_id)
+                        .iter()
+                        .filter(|child| child.ident.name == segment)
+                        .filter_map(|child| child.res.opt_def_id())
+                        .chain(inherent_items(tcx, def_id, segment))
+                        .collect(),
+                    DefKind::Struct | DefKind::Union | DefKind::ForeignTy => {
+                        inherent_items(tcx, def_id, segment).collect()
+                    }
+                    _ => vec![],
+                }
+            })
+            .unique()
+            .collect();
+    }
+    defs
+}
+
+/// The associated items named `name` in the inherent impls of the type `def_id`.
+fn inherent_items(tcx: TyCtxt<'_>, def_id: DefId, name: Symbol) -> impl Iterator<Item = DefId> + '_ {
+    let impls: &[DefId] = match tcx.def_kind(def_id) {
+        DefKind::Enum | DefKind::Struct | DefKind::Union | DefKind::ForeignTy => tcx.inherent_impls(def_id),
+        _ => &[],
+    };
+    impls
+        .iter()
+        .flat_map(move |&impl_| tcx.associated_item_def_ids(impl_))
+        .copied()
+        .filter(move |&item| tcx.item_name(item) == name)
+}
+
+/// A `#![feature]` the crate declares: whether it's a language feature, where it's declared,
+/// and the version it was stabilized in if it's stable by now.
+pub(super) fn declared_feature(tcx: TyCtxt<'_>, feature: Symbol) -> (bool, Span, Option<Symbol>) {
+    let features = tcx.features();
+    if let Some(&(_, span, since)) = features
+        .declared_lang_features
+        .iter()
+        .find(|(name, ..)| *name == feature)
+    {
+        return (true, span, since);
+    }
+
+    let &(_, span) = features
+        .declared_lib_features
+        .iter()
+        .find(|(name, _)| *name == feature)
+        .expect("feature should be declared");
+    let since = std::iter::once(LOCAL_CRATE)
+        .chain(crates(tcx).iter().copied())
+        .find_map(|cnum| match tcx.lib_features(cnum).stability.get(&feature) {
+            Some((FeatureStability::AcceptedSince(since), _)) => Some(*since),
+            _ => None,
+        });
+    (false, span, since)
+}
+
+/// Evaluates the initializer of the `const` or `static` item `def_id`.
+///
+/// Statics can't go through [`TyCtxt::const_eval_poly`], so their allocation is
+/// turned into a [`ConstValue`] the same way constants are represented.
+pub(super) fn eval_const_item<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    adapter: &Adapter<'_>,
+    def_id: DefId,
+) -> Result<ConstValue<'tcx>, ErrorHandled> {
+    if !tcx.is_static(def_id) {
+        return tcx.const_eval_poly(def_id);
+    }
+
+    let alloc = tcx.eval_static_initializer(def_id)?;
+    let ty = tcx.type_of(def_id).instantiate_identity();
+    let layout = tcx.layout_of(ty::ParamEnv::reveal_all().and(ty));
+    if let Ok(layout) = layout {
+        if layout.abi.is_scalar() {
+            // Pointer-sized values like `&T` or `Option<&T>` may hold a pointer, whose provenance
+            // has to be read along with its address.
+            let read_provenance = layout.size == tcx.data_layout.pointer_size;
+            if let Ok(scalar) = alloc.inner().read_scalar(&tcx, alloc_range(Size::ZERO, layout.size), read_provenance) {
+                return Ok(ConstValue::Scalar(scalar));
+            }
+        }
+    }
+
+    // Const eval refuses to read from mutable memory, which is what
+    // a `static mut` is, so destructure a frozen copy of its initial value.
+    let alloc_id = *adapter.static_allocs.borrow_mut().entry(def_id).or_insert_with(|| {
+        let mut snapshot = alloc.inner().clone();
+        snapshot.mutability = Mutability::Not;
+        tcx.reserve_and_set_memory_alloc(tcx.mk_const_alloc(snapshot))
+    });
+    Ok(ConstValue::Indirect { alloc_id, offset: Size::ZERO })
+}
+
+/// Evaluates the `const` or `static` item `def_id`, then descends into its fields along `path`.
+///
+/// Returns [`None`] if evaluation failed or the value doesn't have the fields along `path`.
+pub(super) fn evaluated_const_field<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    adapter: &Adapter<'_>,
+    def_id: DefId,
+    path: &[usize],
+) -> Option<(ConstValue<'tcx>, Ty<'tcx>)> {
+    let value = eval_const_item(tcx, adapter, def_id).ok()?;
+    let ty = tcx.type_of(def_id).instantiate_identity();
+    path.iter().try_fold((value, ty), |(value, ty), index| {
+        destructure_const(tcx, value, ty)?.fields.get(*index).copied()
+    })
+}
+
+/// Splits an array, tuple or ADT value into its variant and fields.
+pub(super) fn destructure_const<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    value: ConstValue<'tcx>,
+    ty: Ty<'tcx>,
+) -> Option<DestructuredConstant<'tcx>> {
+    match ty.kind() {
+        ty::Array(..) | ty::Tuple(..) | ty::Adt(..) if !ty.has_non_region_param() => {
+            tcx.try_destructure_mir_constant_for_user_output(value, ty)
+        }
+        _ => None,
+    }
+}
+
+/// Converts a `bool`, `char`, integer, float or `&str` value into a [`FieldValue`],
+/// or [`FieldValue::Null`] if the value has any other type.
+pub(super) fn const_scalar_to_field_value<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    value: ConstValue<'tcx>,
+    ty: Ty<'tcx>,
+) -> FieldValue {
+    if let ty::Ref(_, inner, _) = ty.kind() {
+        return if inner.is_str() {
+            value
+                .try_get_slice_bytes_for_diagnostics(tcx)
+                .and_then(|bytes| std::str::from_utf8(bytes).ok())
+                .map_or(FieldValue::Null, |s| FieldValue::String(s.into()))
+        } else {
+            FieldValue::Null
+        };
+    }
+
+    let Some(int) = value.try_to_scalar_int() else {
+        return FieldValue::Null;
+    };
+    match ty.kind() {
+        ty::Bool => int.try_to_bool().map_or(FieldValue::Null, FieldValue::Boolean),
+        ty::Char => int
+            .try_to_u32()
+            .ok()
+            .and_then(char::from_u32)
+            .map_or(FieldValue::Null, |c| FieldValue::String(c.to_string().into())),
+        ty::Int(_) => int
+            .try_to_int(int.size())
+            .ok()
+            .and_then(|value| i64::try_from(value).ok())
+            .map_or(FieldValue::Null, FieldValue::Int64),
+        ty::Uint(_) => int
+            .try_to_uint(int.size())
+            .ok()
+            .and_then(|value| u64::try_from(value).ok())
+            .map_or(FieldValue::Null, FieldValue::Uint64),
+        ty::Float(ty::FloatTy::F32) => int
+            .try_to_u32()
+            .map_or(FieldValue::Null, |bits| FieldValue::Float64(f32::from_bits(bits).into())),
+        ty::Float(ty::FloatTy::F64) => int
+            .try_to_u64()
+            .map_or(FieldValue::Null, |bits| FieldValue::Float64(f64::from_bits(bits))),
+        _ => FieldValue::Null,
+    }
+}
+
+/// Descends into the nested meta items of `attr` along `path`.
+pub(super) fn nested_meta_item(attr: &Attribute, path: &[usize]) -> Option<NestedMetaItem> {
+    let (first, rest) = path.split_first()?;
+    let item = attr.meta_item_list()?.get(*first)?.clone();
+    rest.iter().try_fold(item, |item, index| item.meta_item_list()?.get(*index).cloned())
+}
+
+/// Concatenates the doc comments and `#[doc]` attributes among `attrs` the way rustdoc does.
+pub(super) fn docs(attrs: &[Attribute]) -> Option<String> {
+    let (fragments, _) = rustdoc::attrs_to_doc_fragments(attrs.iter().map(|attr| (attr, None)), true);
+    if fragments.is_empty() {
+        return None;
+    }
+
+    let mut docs = String::new();
+    for fragment in &fragments {
+        rustdoc::add_doc_fragment(&mut docs, fragment);
+    }
+    docs.truncate(docs.trim_end_matches('\n').len());
+    Some(docs)
+}
+
+/// The paths of the intra-doc links in the docs among `attrs`, preprocessed the same way
+/// `rustc_resolve` does before resolving them, e.g. `[`Vec::new()`]` becomes `Vec::new`.
+///
+/// Links whose destination contains a `/` are URLs or file paths, and are skipped.
+pub(super) fn doc_links(attrs: &[Attribute]) -> Vec<String> {
+    let Some(doc) = docs(attrs) else {
+        return vec![];
+    };
+
+    markdown_link_destinations(&doc)
+        .into_iter()
+        .filter(|dest| !dest.is_empty() && !dest.contains('/'))
+        .map(|dest| preprocess_doc_link(&dest))
+        .collect()
+}
+
+/// The destinations of the links in `doc` that may be intra-doc links, including
+/// shortcut links like `[Vec]` that have no destination in the Markdown itself.
+fn markdown_link_destinations<'md>(doc: &'md str) -> Vec<CowStr<'md>> {
+    let mut broken_link_callback = |link: BrokenLink<'md>| Some((link.reference, "".into()));
+    Parser::new_with_broken_link_callback(
+        doc,
+        rustdoc::main_body_opts(),
+        Some(&mut broken_link_callback),
+    )
+        .filter_map(|event| match event {
+            Event::Start(Tag::Link(link_type, dest, _)) if rustdoc::may_be_doc_link(link_type) => {
+                Some(dest)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// A copy of `rustc_resolve::rustdoc::preprocess_link`, which isn't public.
+fn preprocess_doc_link(link: &str) -> String {
+    let link = link.replace('`', "");
+    let link = link.split('#').next().unwrap();
+    let link = link.trim();
+    let link = link.rsplit('@').next().unwrap();
+    let link = link.strip_suffix("()").unwrap_or(link);
+    let link = link.strip_suffix("{}").unwrap_or(link);
+    let link = link.strip_suffix("[]").unwrap_or(link);
+    let link = if link != "!" { link.strip_suffix('!').unwrap_or(link) } else { link };
+    let link = link.trim();
+    rustdoc::strip_generics_from_path(link).map_or_else(|_| link.into(), String::from)
+}
+
+/// Looks up what an intra-doc link on `def_id` was resolved to, in the module `def_id` is in.
+///
+/// Like everything else, the outer docs of a module are resolved in its parent, only docs inside
+/// the module are resolved in the module itself. `None` if the link wasn't resolved, including when
+/// rustc didn't resolve the links of the crate at all.
+pub(super) fn resolve_doc_link(tcx: TyCtxt<'_>, def_id: DefId, link: &str) -> Option<DefId> {
+    let start = if tcx.def_kind(def_id) == DefKind::Mod && !rustdoc::inner_docs(tcx.get_attrs_unchecked(def_id)) {
+        tcx.opt_parent(def_id)
+    } else {
+        Some(def_id)
+    };
+    let module = std::iter::successors(start, |id| tcx.opt_parent(*id))
+        .find(|id| tcx.def_kind(*id) == DefKind::Mod)?;
+    let resolutions = match module.as_local() {
+        // The query `span_bug`s for modules nothing was resolved in.
+        Some(module) => tcx.resolutions(()).doc_link_resolutions.get(&module)?,
+        // Other crates only keep the resolutions of the docs of their exported items.
+        None if tcx.visibility(def_id).is_public() => tcx.doc_link_resolutions(module),
+        None => return None,
+    };
+    let link = Symbol::intern(link);
+    [Namespace::TypeNS, Namespace::ValueNS, Namespace::MacroNS]
+        .into_iter()
+        .find_map(|ns| match resolutions.get(&(link, ns)) {
+            Some(Some(Res::Def(_, def_id))) => Some(*def_id),
+            _ => None,
+        })
+}
+
+/// Collects the expressions and patterns of a THIR body in the order they're visited,
+/// either all of them or only the ones directly nested in where the walk starts.
+struct ThirCollector<'a, 'tcx> {
+    thir: &'a Thir<'tcx>,
+    recurse: bool,
+    exprs: Vec<&'a thir::Expr<'tcx>>,
+    pats: Vec<&'a thir::Pat<'tcx>>,
+}
+
+impl<'a, 'tcx> ThirCollector<'a, 'tcx> {
+    fn new(thir: &'a Thir<'tcx>, recurse: bool) -> Self {
+        Self { thir, recurse, exprs: vec![], pats: vec![] }
+    }
+}
+
+impl<'a, 'tcx> Visitor<'a, 'tcx> for ThirCollector<'a, 'tcx> {
+    fn thir(&self) -> &'a Thir<'tcx> {
+        self.thir
+    }
+
+    fn visit_expr(&mut self, expr: &'a thir::Expr<'tcx>) {
+        self.exprs.push(expr);
+        if self.recurse {
+            walk_expr(self, expr);
+        }
+    }
+
+    fn visit_pat(&mut self, pat: &'a thir::Pat<'tcx>) {
+        self.pats.push(pat);
+        if self.recurse {
+            walk_pat(self, pat);
+        }
+    }
+}
+
+/// The name of the enum variant `value` is, e.g. `Call` for a THIR `ExprKind::Call { .. }`.
+pub(super) fn variant_name(value: &impl std::fmt::Debug) -> String {
+    let debug = format!("{value:?}");
+    let end = debug.find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(debug.len());
+    debug[..end].to_string()
+}
+
+/// The patterns of a THIR body, parameters first, in the order they're visited.
+///
+/// Unlike expressions, THIR patterns have no ids, so their index in this list is
+/// how they're referred to.
+pub(super) fn thir_pats<'a, 'tcx>(thir: &'a Thir<'tcx>, body: ExprId) -> Vec<&'a thir::Pat<'tcx>> {
+    let mut collector = ThirCollector::new(thir, true);
+    for pat in thir.params.iter().filter_map(|param| param.pat.as_deref()) {
+        collector.visit_pat(pat);
+    }
+    collector.visit_expr(&thir[body]);
+    collector.pats
+}
+
+/// The index of `pat` in [`thir_pats`].
+pub(super) fn thir_pat_index<'tcx>(thir: &Thir<'tcx>, body: ExprId, pat: &thir::Pat<'tcx>) -> usize {
+    thir_pats(thir, body)
+        .into_iter()
+        .position(|candidate| std::ptr::eq(candidate, pat))
+        .expect("pattern should be part of the body")
+}
+
+/// The patterns directly nested in the pattern at `index` in [`thir_pats`], as indices into it.
+pub(super) fn thir_subpatterns(thir: &Thir<'_>, body: ExprId, index: usize) -> Vec<usize> {
+    let pats = thir_pats(thir, body);
+    let mut collector = ThirCollector::new(thir, false);
+    walk_pat(&mut collector, pats[index]);
+    collector.pats
+        .into_iter()
+        .map(|pat| pats.iter().position(|candidate| std::ptr::eq(*candidate, pat)).expect("pattern should be part of the body"))
+        .collect()
+}
+
+/// The expressions nested in `expr` at any depth, in the order they're visited.
+pub(super) fn thir_descendants(thir: &Thir<'_>, expr: ExprId) -> Vec<ExprId> {
+    let ids: FxHashMap<*const thir::Expr<'_>, ExprId> = thir.exprs
+        .iter_enumerated()
+        .map(|(id, expr)| (expr as *const _, id))
+        .collect();
+    let mut collector = ThirCollector::new(thir, true);
+    walk_expr(&mut collector, &thir[expr]);
+    collector.exprs
+        .into_iter()
+        .map(|expr| ids[&(expr as *const _)])
+        .collect()
+}
+
+/// The function the call that ends `block` calls. Trait methods resolve to the method of the impl
+/// that is called, as long as the impl can be known without knowing the function's generic parameters.
+/// `None` if `block` doesn't end in a call, or if the call is of a value only known at runtime.
+pub(super) fn mir_callee<'tcx>(tcx: TyCtxt<'tcx>, body: &mir::Body<'tcx>, block: BasicBlock) -> Option<DefId> {
+    let TerminatorKind::Call { func, .. } = &body.basic_blocks[block].terminator().kind else {
+        return None;
+    };
+    let (def_id, args) = func.const_fn_def()?;
+    let param_env = tcx.param_env(body.source.def_id());
+    match Instance::resolve(tcx, param_env, def_id, args) {
+        Ok(Some(instance)) => Some(instance.def_id()),
+        _ => Some(def_id),
+    }
+}
+
+/// How the terminator of `block` can panic by itself, without looking into the local functions it calls.
+fn direct_panic_kind<'tcx>(tcx: TyCtxt<'tcx>, body: &mir::Body<'tcx>, block: BasicBlock) -> Option<PanicKind> {
+    if let TerminatorKind::Assert { .. } = body.basic_blocks[block].terminator().kind {
+        return Some(PanicKind::Assert);
+    }
+    let callee = mir_callee(tcx, body, block)?;
+
+    let is_panicking_module = [sym::core, sym::std].contains(&tcx.crate_name(callee.krate))
+        && tcx.def_path(callee).data.first().is_some_and(|segment| {
+            segment.data.get_opt_name() == Some(Symbol::intern("panicking"))
+        });
+    let trait_ = tcx
+        .trait_of_item(callee)
+        .or_else(|| tcx.impl_of_method(callee).and_then(|impl_| tcx.trait_id_of_impl(impl_)));
+    let lang_items = tcx.lang_items();
+
+    if is_panicking_module {
+        Some(PanicKind::Panic)
+    } else if trait_.is_some() && (trait_ == lang_items.index_trait() || trait_ == lang_items.index_mut_trait()) {
+        Some(PanicKind::Index)
+    } else if !callee.is_local() && tcx.codegen_fn_attrs(callee).flags.contains(CodegenFnAttrFlags::TRACK_CALLER) {
+        Some(PanicKind::TrackCaller)
+    } else {
+        None
+    }
+}
+
+/// The local functions `body` calls whose MIR there is to look into.
+fn local_callees<'a>(tcx: TyCtxt<'a>, body: &'a mir::Body<'a>) -> impl Iterator<Item = LocalDefId> + 'a {
+    body.basic_blocks.indices().filter_map(move |block| {
+        let callee = mir_callee(tcx, body, block)?.as_local()?;
+        tcx.is_mir_available(callee).then_some(callee)
+    })
+}
+
+/// Whether `def_id` or any local function it calls, directly or not, has a panic site.
+pub(super) fn may_panic(tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
+    let mut visited = FxHashSet::default();
+    let mut stack = vec![def_id];
+    while let Some(def_id) = stack.pop() {
+        if !visited.insert(def_id) {
+            continue;
+        }
+        let body = tcx.optimized_mir(def_id);
+        if body.basic_blocks.indices().any(|block| direct_panic_kind(tcx, body, block).is_some()) {
+            return true;
+        }
+        stack.extend(local_callees(tcx, body));
+    }
+    false
+}
+
+/// The blocks of the optimized MIR of `def_id` whose terminator can panic, and how.
+pub(super) fn panic_sites(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Vec<(BasicBlock, PanicKind)> {
+    let body = tcx.optimized_mir(def_id);
+    body.basic_blocks
+        .indices()
+        .filter_map(|block| {
+            direct_panic_kind(tcx, body, block).or_else(|| {
+                let callee = mir_callee(tcx, body, block)?.as_local()?;
+                (tcx.is_mir_available(callee) && may_panic(tcx, callee)).then_some(PanicKind::Call)
+            })
+            .map(|kind| (block, kind))
+        })
+        .collect()
+}
+
+/// Whether `expr` is a block the user wrote `unsafe` on.
+pub(super) fn is_unsafe_block(expr: &rustc_hir::Expr<'_>) -> bool {
+    matches!(
+        expr.kind,
+        rustc_hir::ExprKind::Block(rustc_hir::Block {
+            rules: BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided),
+            ..
+        }, _)
+    )
+}
+
+/// What kind of operation `expr` is if it requires `unsafe`, the way rustc's THIR unsafety checking
+/// tells them apart. Operations that only require `unsafe` because of `#[target_feature]` or
+/// `#[rustc_layout_scalar_valid_range_*]` aren't told apart.
+pub(super) fn unsafe_operation_kind<'tcx>(tcx: TyCtxt<'tcx>, thir: &Thir<'tcx>, expr: ExprId) -> Option<&'static str> {
+    match thir[expr].kind {
+        thir::ExprKind::Call { fun, .. } if thir[fun].ty.fn_sig(tcx).unsafety() == Unsafety::Unsafe => {
+            Some("call_to_unsafe_function")
+        }
+        thir::ExprKind::Deref { arg } => match thir[arg].kind {
+            thir::ExprKind::StaticRef { def_id, .. } | thir::ExprKind::ThreadLocalRef(def_id) => {
+                if tcx.is_mutable_static(def_id) {
+                    Some("use_of_mutable_static")
+                } else if tcx.is_foreign_item(def_id) {
+                    Some("use_of_extern_static")
+                } else {
+                    None
+                }
+            }
+            _ => thir[arg].ty.is_unsafe_ptr().then_some("deref_of_raw_pointer"),
+        },
+        thir::ExprKind::Field { lhs, .. } if thir[lhs].ty.ty_adt_def().is_some_and(|adt| adt.is_union()) => {
+            Some("access_to_union_field")
+        }
+        thir::ExprKind::InlineAsm(..) => Some("use_of_inline_assembly"),
+        _ => None,
+    }
+}
+
+/// The operations that require `unsafe` which `block` is the innermost `unsafe` block of,
+/// as the body owner they're in and their expression, including ones in closures in `block`.
+pub(super) fn unsafe_operations(tcx: TyCtxt<'_>, block: HirId) -> Vec<(LocalDefId, ExprId)> {
+    let hir = tcx.hir();
+    let rustc_hir::ExprKind::Block(hir_block, _) = hir.expect_expr(block).kind else {
+        unreachable!("{block:?} isn't a block");
+    };
+    // THIR refers to the block itself rather than to the expression it's in.
+    let block = hir_block.hir_id;
+    let def_id = hir.enclosing_body_owner(block);
+    with_thir(tcx, def_id, |thir, _| {
+        let expr = thir.exprs.iter_enumerated().find_map(|(id, expr)| match expr.kind {
+            thir::ExprKind::Block { block: thir_block }
+                if matches!(thir[thir_block].safety_mode, BlockSafety::ExplicitUnsafe(hir_id) if hir_id == block) => Some(id),
+            _ => None,
+        });
+        expr.map_or_else(Vec::new, |expr| unsafe_operations_in(tcx, def_id, thir, expr))
+    })
+    .unwrap_or_default()
+}
+
+fn unsafe_operations_in<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: LocalDefId,
+    thir: &Thir<'tcx>,
+    expr: ExprId,
+) -> Vec<(LocalDefId, ExprId)> {
+    let descendants = thir_descendants(thir, expr);
+
+    let mut skipped = FxHashSet::default();
+    for &descendant in &descendants {
+        match thir[descendant].kind {
+            // Nested `unsafe` blocks own the operations in them, and so do the ones the compiler generates.
+            thir::ExprKind::Block { block } if !matches!(thir[block].safety_mode, BlockSafety::Safe) => {
+                skipped.extend(thir_descendants(thir, descendant));
+            }
+            // Assigning to a union field is safe, it's only reading one that isn't.
+            thir::ExprKind::Assign { mut lhs, .. } => loop {
+                match thir[lhs].kind {
+                    thir::ExprKind::Scope { value, .. } => lhs = value,
+                    thir::ExprKind::Field { lhs: base, .. } => {
+                        skipped.insert(lhs);
+                        lhs = base;
+                    }
+                    _ => break,
+                }
+            },
+            _ => {}
+        }
+    }
+
+    let mut operations = vec![];
+    for descendant in descendants.into_iter().filter(|descendant| !skipped.contains(descendant)) {
+        if let thir::ExprKind::Closure(closure) = &thir[descendant].kind {
+            let closure_id = closure.closure_id;
+            operations.extend(
+                with_thir(tcx, closure_id, |thir, body| unsafe_operations_in(tcx, closure_id, thir, body))
+                    .unwrap_or_default()
+            );
+        } else if unsafe_operation_kind(tcx, thir, descendant).is_some() {
+            operations.push((def_id, descendant));
+        }
+    }
+    operations
+}
+
+/// The places `body` moves out of, in the order they're visited, with where they're moved.
+pub(super) fn mir_moves<'tcx>(body: &mir::Body<'tcx>) -> Vec<(Location, Place<'tcx>)> {
+    struct Moves<'tcx>(Vec<(Location, Place<'tcx>)>);
+
+    impl<'tcx> mir::visit::Visitor<'tcx> for Moves<'tcx> {
+        fn visit_operand(&mut self, operand: &Operand<'tcx>, location: Location) {
+            if let Operand::Move(place) = operand {
+                self.0.push((location, *place));
+            }
+        }
+    }
+
+    let mut moves = Moves(vec![]);
+    mir::visit::Visitor::visit_body(&mut moves, body);
+    moves.0
+}
+
+/// The items codegen instantiates, codegen unit by codegen unit, with the name of their codegen
+/// unit. Items that are inlined into several codegen units show up once per codegen unit.
+pub(super) fn mono_items(tcx: TyCtxt<'_>) -> Vec<(MonoItem<'_>, MonoItemData, Symbol)> {
+    let (_, units) = tcx.collect_and_partition_mono_items(());
+    units
+        .iter()
+        .flat_map(|unit| {
+            unit.items_in_deterministic_order(tcx)
+                .into_iter()
+                .map(|(item, data)| (item, data, unit.name()))
+        })
+        .collect()
+}
+
+/// The generic argument types of a mono item, empty for statics and `global_asm!`.
+pub(super) fn mono_item_arg_tys(item: MonoItem<'_>) -> Vec<Ty<'_>> {
+    match item {
+        MonoItem::Fn(instance) => instance.args.types().collect(),
+        MonoItem::Static(_) | MonoItem::GlobalAsm(_) => vec![],
+    }
+}
+
+/// Where each local of `body` that's dropped is first dropped, outside of unwinding, and in which
+/// position among the locals dropped at the end of the same scope, e.g. `0` for the local that was
+/// declared last in a block.
+pub(super) fn mir_drops(body: &mir::Body<'_>) -> FxHashMap<Local, (Span, usize)> {
+    // Every `let` opens a source scope of its own, so locals dropped at the end of the same block
+    // are told apart from others by where they're dropped instead.
+    let mut dropped_at: FxHashMap<Span, usize> = FxHashMap::default();
+    let mut drops = FxHashMap::default();
+    for &block in body.basic_blocks.reverse_postorder() {
+        let data = &body.basic_blocks[block];
+        if data.is_cleanup {
+            continue;
+        }
+        let terminator = data.terminator();
+        let TerminatorKind::Drop { place, .. } = terminator.kind else {
+            continue;
+        };
+        let Some(local) = place.as_local() else {
+            continue;
+        };
+        if drops.contains_key(&local) {
+            continue;
+        }
+        let order = dropped_at.entry(terminator.source_info.span).or_default();
+        drops.insert(local, (terminator.source_info.span, *order));
+        *order += 1;
+    }
+    drops
+}
@@ -1,27 +1,314 @@
-use rustc_hir::{ItemId, HirId, BodyId};
-use rustc_span::def_id::DefId;
+use rustc_ast::Attribute;
+use rustc_borrowck::consumers::BorrowIndex;
+use rustc_hir::{
+    ItemId, HirId, BodyId, Closure, ClosureKind, CoroutineDesugaring, CoroutineKind, Expr,
+    ExprKind, ImplItem, ImplItemKind, Item, ItemKind, LangItem, MatchSource, Node, QPath, StmtKind,
+};
+use rustc_middle::{
+    mir::{self, BasicBlock, CoroutineSavedLocal, Local, Location},
+    thir::{self, ArmId, ExprId, StmtId},
+    ty::{self, TyCtxt},
+};
+use rustc_span::{def_id::{CrateNum, DefId, LocalDefId}, Symbol};
+
+use crate::query_overrides::{borrowck_facts, built_mir, with_thir};
+
+use super::{util::{mono_item_arg_tys, thir_pats}, Adapter};
 
 #[non_exhaustive]
 #[derive(Debug, Clone, trustfall::provider::TrustfallEnumVertex)]
 pub enum Vertex {
+    AssignOp(HirId),
+    Attribute(AttrOrigin),
+    AsyncBlock(HirId),
+    Await(HirId),
+    Binary(HirId),
     Block(HirId),
     Body(BodyId),
-    Crate(()),
+    Const(ItemId),
+    Crate(CrateNum),
     Expr(HirId),
+    Feature(Symbol),
     Fn(ItemId),
     FnBody(BodyId),
+    Impl(ItemId),
+    Index(HirId),
     Item(ItemId),
+    Lit(HirId),
     LocalStatement(HirId),
     Node(HirId),
     Statement(HirId),
-    Ty(HirId),
+    Static(ItemId),
+    Ty(TyOrigin),
     MethodCall(HirId),
+    Unary(HirId),
     Def(DefId),
-    Stability(DefId),
-    ConstStability(DefId),
+    DocLink(DefId, usize),
+    StableLevel(DefId),
+    UnstableLevel(DefId),
+    ConstStableLevel(DefId),
+    ConstUnstableLevel(DefId),
+    Deprecation(DefId),
+    ConstValue(DefId, Vec<usize>),
+    MetaItem(AttrOrigin, Vec<usize>),
+    CoroutineLayout(DefId),
+    CoroutineSavedLocal(DefId, CoroutineSavedLocal),
+    BorrowAdjustment(HirId, usize),
+    DerefAdjustment(HirId, usize),
+    DynStarAdjustment(HirId, usize),
+    NeverToAnyAdjustment(HirId, usize),
+    PointerAdjustment(HirId, usize),
+    Mir(MirOrigin),
+    MirLocal(MirOrigin, Local),
+    BasicBlock(MirOrigin, BasicBlock),
+    MirStatement(MirOrigin, Location),
+    Terminator(MirOrigin, BasicBlock),
+    CallTerminator(MirOrigin, BasicBlock),
+    DropTerminator(MirOrigin, BasicBlock),
+    AssertTerminator(MirOrigin, BasicBlock),
+    Thir(LocalDefId),
+    ThirExpr(LocalDefId, ExprId),
+    ThirBlock(LocalDefId, ExprId),
+    ThirBorrow(LocalDefId, ExprId),
+    ThirCall(LocalDefId, ExprId),
+    ThirMatch(LocalDefId, ExprId),
+    ThirArm(LocalDefId, ArmId),
+    ThirStmt(LocalDefId, StmtId),
+    ThirPat(LocalDefId, usize),
+    PanicSite(MirOrigin, BasicBlock, PanicKind),
+    UnsafeOperation(LocalDefId, ExprId),
+    Borrowck(LocalDefId),
+    Borrow(LocalDefId, BorrowIndex),
+    RegionConstraint(LocalDefId, usize),
+    Move(LocalDefId, usize),
+    Instance(usize),
+}
+
+/// Which MIR body of a function a MIR vertex belongs to, so that the
+/// [`mir::Body`] can be looked up again from a [`TyCtxt`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy)]
+pub enum MirOrigin {
+    /// The MIR as it's built from THIR, before any analysis or optimization ran on it.
+    Built(LocalDefId),
+    /// The MIR codegen uses.
+    Optimized(LocalDefId),
+    /// The MIR borrowck ran on, whose regions are the ones in borrowck facts.
+    Borrowck(LocalDefId),
+}
+
+impl MirOrigin {
+    pub fn body(self, ctxt: TyCtxt<'_>) -> &mir::Body<'_> {
+        match self {
+            Self::Built(def_id) => built_mir(ctxt, def_id).expect("built MIR should have been kept"),
+            Self::Optimized(def_id) => ctxt.optimized_mir(def_id),
+            Self::Borrowck(def_id) => &borrowck_facts(ctxt, def_id).expect("borrowck facts should have been kept").body,
+        }
+    }
+}
+
+/// Why the terminator of a [`Vertex::PanicSite`] can panic.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanicKind {
+    /// An `Assert` terminator, e.g. a bounds or overflow check.
+    Assert,
+    /// A call of a function of `core::panicking` or `std::panicking`, which is what `panic!`,
+    /// `unreachable!` and friends expand to.
+    Panic,
+    /// A call of an `Index` or `IndexMut` impl.
+    Index,
+    /// A call of a `#[track_caller]` function of another crate, e.g. `Option::unwrap`.
+    TrackCaller,
+    /// A call of a local function that may panic.
+    Call,
+}
+
+impl PanicKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Assert => "assert",
+            Self::Panic => "panic",
+            Self::Index => "index",
+            Self::TrackCaller => "track_caller",
+            Self::Call => "call",
+        }
+    }
+}
+
+/// Where a [`Vertex::Ty`] was found, so that the [`ty::Ty`] itself can be
+/// looked up again from a [`TyCtxt`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy)]
+pub enum TyOrigin {
+    /// The type typeck assigned to a HIR node.
+    Node(HirId),
+    /// The type of a local saved across a suspension point of a coroutine.
+    CoroutineSavedLocal(DefId, CoroutineSavedLocal),
+    /// The type of an expression after all of its adjustments were applied.
+    Adjusted(HirId),
+    /// The target type of the n-th adjustment applied to an expression.
+    Adjustment(HirId, usize),
+    /// The declared type of an item, e.g. of a `const` or `static`.
+    TypeOf(DefId),
+    /// The type of a local of a MIR body.
+    MirLocal(MirOrigin, Local),
+    /// The type of the place a MIR `Drop` terminator drops.
+    DroppedPlace(MirOrigin, BasicBlock),
+    /// The type of a THIR expression.
+    ThirExpr(LocalDefId, ExprId),
+    /// The type of the n-th pattern of a THIR body, in the order they're visited.
+    ThirPat(LocalDefId, usize),
+    /// The n-th generic argument type of the n-th item codegen instantiates.
+    InstanceArg(usize, usize),
+}
+
+impl TyOrigin {
+    pub fn ty<'tcx>(self, ctxt: TyCtxt<'tcx>, adapter: &Adapter<'tcx>) -> ty::Ty<'tcx> {
+        match self {
+            Self::Node(hir_id) => {
+                let def_id = ctxt.hir().enclosing_body_owner(hir_id);
+                ctxt.typeck(def_id).node_type(hir_id)
+            }
+            Self::CoroutineSavedLocal(def_id, local) => {
+                ctxt
+                    .coroutine_layout(def_id)
+                    .expect("coroutine should have a layout")
+                    .field_tys[local]
+                    .ty
+            }
+            Self::Adjusted(hir_id) => {
+                let hir = ctxt.hir();
+                let def_id = hir.enclosing_body_owner(hir_id);
+                ctxt.typeck(def_id).expr_ty_adjusted(hir.expect_expr(hir_id))
+            }
+            Self::Adjustment(hir_id, index) => {
+                let def_id = ctxt.hir().enclosing_body_owner(hir_id);
+                ctxt.typeck(def_id).adjustments()[hir_id][index].target
+            }
+            Self::TypeOf(def_id) => ctxt.type_of(def_id).instantiate_identity(),
+            Self::MirLocal(origin, local) => origin.body(ctxt).local_decls[local].ty,
+            Self::DroppedPlace(origin, block) => {
+                let body = origin.body(ctxt);
+                let mir::TerminatorKind::Drop { place, .. } = body.basic_blocks[block].terminator().kind else {
+                    panic!("{block:?} doesn't end in a drop");
+                };
+                place.ty(body, ctxt).ty
+            }
+            Self::ThirExpr(def_id, expr) => {
+                with_thir(ctxt, def_id, |thir, _| thir[expr].ty).expect("THIR should have been kept")
+            }
+            Self::ThirPat(def_id, index) => {
+                with_thir(ctxt, def_id, |thir, body| thir_pats(thir, body)[index].ty)
+                    .expect("THIR should have been kept")
+            }
+            Self::InstanceArg(instance, index) => {
+                let (item, _, _) = adapter.mono_items(ctxt)[instance];
+                mono_item_arg_tys(item)[index]
+            }
+        }
+    }
+
+    /// The param env of the definition the type was found in, for the questions about the type
+    /// that depend on the bounds its generic parameters are known to satisfy.
+    pub fn param_env(self, ctxt: TyCtxt<'_>) -> ty::ParamEnv<'_> {
+        let def_id = match self {
+            Self::Node(hir_id) | Self::Adjusted(hir_id) | Self::Adjustment(hir_id, _) => {
+                ctxt.hir().enclosing_body_owner(hir_id).to_def_id()
+            }
+            Self::CoroutineSavedLocal(def_id, _) | Self::TypeOf(def_id) => def_id,
+            Self::MirLocal(origin, _) | Self::DroppedPlace(origin, _) => origin.body(ctxt).source.def_id(),
+            Self::ThirExpr(def_id, _) | Self::ThirPat(def_id, _) => def_id.to_def_id(),
+            // Instances have no generic parameters left.
+            Self::InstanceArg(..) => return ty::ParamEnv::reveal_all(),
+        };
+        ctxt.param_env(def_id)
+    }
+}
+
+/// Where a [`Vertex::Attribute`] was found, so that the [`Attribute`] itself can be
+/// looked up again from a [`TyCtxt`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy)]
+pub enum AttrOrigin {
+    /// The n-th attribute on a HIR node.
+    Node(HirId, usize),
+    /// The n-th attribute on a definition, which may be in another crate.
+    Def(DefId, usize),
+}
+
+impl AttrOrigin {
+    pub fn attr(self, ctxt: TyCtxt<'_>) -> &Attribute {
+        match self {
+            Self::Node(hir_id, index) => &ctxt.hir().attrs(hir_id)[index],
+            Self::Def(def_id, index) => &ctxt.get_attrs_unchecked(def_id)[index],
+        }
+    }
 }
 
 impl Vertex {
+    /// The most specific vertex type for an item.
+    pub(super) fn from_item(item: &Item<'_>) -> Self {
+        match item.kind {
+            ItemKind::Fn(..) => Self::Fn(item.item_id()),
+            ItemKind::Const(..) => Self::Const(item.item_id()),
+            ItemKind::Static(..) => Self::Static(item.item_id()),
+            ItemKind::Impl(..) => Self::Impl(item.item_id()),
+            _ => Self::Item(item.item_id()),
+        }
+    }
+
+    /// The vertex for a function of an impl block, which the schema treats as a [`Vertex::Fn`]
+    /// as well. `None` for associated consts and types, which have no type of their own.
+    pub(super) fn from_impl_item(impl_item: &ImplItem<'_>) -> Option<Self> {
+        match impl_item.kind {
+            ImplItemKind::Fn(..) => Some(Self::Fn(ItemId { owner_id: impl_item.owner_id })),
+            _ => None,
+        }
+    }
+
+    /// The most specific vertex type for an expression.
+    pub(super) fn from_expr(expr: &Expr<'_>) -> Self {
+        match expr.kind {
+            ExprKind::MethodCall(..) => Self::MethodCall(expr.hir_id),
+            ExprKind::Binary(..) => Self::Binary(expr.hir_id),
+            ExprKind::AssignOp(..) => Self::AssignOp(expr.hir_id),
+            ExprKind::Unary(..) => Self::Unary(expr.hir_id),
+            ExprKind::Index(..) => Self::Index(expr.hir_id),
+            ExprKind::Lit(..) => Self::Lit(expr.hir_id),
+            ExprKind::Block(..) => Self::Block(expr.hir_id),
+            // The polling loop of an `.await` is an `AwaitDesugar` match as well,
+            // only the outer one matches on `IntoFuture::into_future(<expr>)`.
+            ExprKind::Match(Expr {
+                kind: ExprKind::Call(Expr {
+                    kind: ExprKind::Path(QPath::LangItem(LangItem::IntoFutureIntoFuture, ..)),
+                    ..
+                }, _),
+                ..
+            }, _, MatchSource::AwaitDesugar) => Self::Await(expr.hir_id),
+            ExprKind::Closure(Closure {
+                kind: ClosureKind::Coroutine(CoroutineKind::Desugared(CoroutineDesugaring::Async, _)),
+                ..
+            }) => Self::AsyncBlock(expr.hir_id),
+            _ => Self::Expr(expr.hir_id),
+        }
+    }
+
+    /// The most specific vertex type for a HIR node, falling back to [`Vertex::Node`]
+    /// for nodes the schema has no type for.
+    pub(super) fn from_hir_node(ctxt: TyCtxt<'_>, hir_id: HirId) -> Self {
+        match ctxt.hir_node(hir_id) {
+            Node::Item(item) => Self::from_item(item),
+            Node::ImplItem(impl_item) => Self::from_impl_item(impl_item).unwrap_or(Self::Node(hir_id)),
+            Node::Expr(expr) => Self::from_expr(expr),
+            Node::Stmt(stmt) => match stmt.kind {
+                StmtKind::Local(..) => Self::LocalStatement(hir_id),
+                _ => Self::Statement(hir_id),
+            },
+            _ => Self::Node(hir_id),
+        }
+    }
+
     pub fn hir_id(&self) -> Option<HirId> {
         if let Some(item_id) = self.clone().item_id() {
             Some(item_id.hir_id())
@@ -30,9 +317,16 @@ impl Vertex {
         } else {
             match self {
                 Self::Node(hir_id)
+                | Self::AssignOp(hir_id)
+                | Self::AsyncBlock(hir_id)
+                | Self::Await(hir_id)
+                | Self::Binary(hir_id)
                 | Self::Block(hir_id)
                 | Self::Expr(hir_id)
+                | Self::Index(hir_id)
+                | Self::Lit(hir_id)
                 | Self::MethodCall(hir_id)
+                | Self::Unary(hir_id)
                 | Self::Statement(hir_id)
                 | Self::LocalStatement(hir_id) => Some(*hir_id),
                 _ => None,
@@ -43,7 +337,10 @@ impl Vertex {
     pub fn item_id(&self) -> Option<ItemId> {
         match self {
             Self::Item(item_id)
-            | Self::Fn(item_id) => Some(*item_id),
+            | Self::Const(item_id)
+            | Self::Fn(item_id)
+            | Self::Impl(item_id)
+            | Self::Static(item_id) => Some(*item_id),
             _ => None,
         }
     }
@@ -56,6 +353,59 @@ impl Vertex {
         }
     }
 
+    pub fn adjustment(&self) -> Option<(HirId, usize)> {
+        match self {
+            Self::BorrowAdjustment(hir_id, index)
+            | Self::DerefAdjustment(hir_id, index)
+            | Self::DynStarAdjustment(hir_id, index)
+            | Self::NeverToAnyAdjustment(hir_id, index)
+            | Self::PointerAdjustment(hir_id, index) => Some((*hir_id, *index)),
+            _ => None,
+        }
+    }
+
+    pub fn terminator(&self) -> Option<(MirOrigin, BasicBlock)> {
+        match self {
+            Self::Terminator(origin, block)
+            | Self::CallTerminator(origin, block)
+            | Self::DropTerminator(origin, block)
+            | Self::AssertTerminator(origin, block) => Some((*origin, *block)),
+            _ => None,
+        }
+    }
+
+    /// The most specific vertex type for the terminator of `block`.
+    pub(super) fn from_terminator(ctxt: TyCtxt<'_>, origin: MirOrigin, block: BasicBlock) -> Self {
+        match origin.body(ctxt).basic_blocks[block].terminator().kind {
+            mir::TerminatorKind::Call { .. } => Self::CallTerminator(origin, block),
+            mir::TerminatorKind::Drop { .. } => Self::DropTerminator(origin, block),
+            mir::TerminatorKind::Assert { .. } => Self::AssertTerminator(origin, block),
+            _ => Self::Terminator(origin, block),
+        }
+    }
+
+    pub fn thir_expr(&self) -> Option<(LocalDefId, ExprId)> {
+        match self {
+            Self::ThirExpr(def_id, expr)
+            | Self::ThirBlock(def_id, expr)
+            | Self::ThirBorrow(def_id, expr)
+            | Self::ThirCall(def_id, expr)
+            | Self::ThirMatch(def_id, expr) => Some((*def_id, *expr)),
+            _ => None,
+        }
+    }
+
+    /// The most specific vertex type for a THIR expression.
+    pub(super) fn from_thir_expr(thir: &thir::Thir<'_>, def_id: LocalDefId, expr: ExprId) -> Self {
+        match thir[expr].kind {
+            thir::ExprKind::Block { .. } => Self::ThirBlock(def_id, expr),
+            thir::ExprKind::Borrow { .. } => Self::ThirBorrow(def_id, expr),
+            thir::ExprKind::Call { .. } => Self::ThirCall(def_id, expr),
+            thir::ExprKind::Match { .. } => Self::ThirMatch(def_id, expr),
+            _ => Self::ThirExpr(def_id, expr),
+        }
+    }
+
     pub fn def_id(&self) -> Option<DefId> {
         match self {
             Self::Def(def_id) => Some(*def_id),
@@ -3,8 +3,9 @@ use std::sync::atomic::AtomicBool;
 use rustc_interface::Config;
 use rustc_errors::registry;
 use rustc_session::config;
+use rustc_span::edition::{Edition, DEFAULT_EDITION};
 
-use crate::util::get_sysroot;
+use crate::{query_overrides::{override_queries, override_thir_queries}, util::get_sysroot};
 
 /// A configuration struct for rustc that can be converted into
 /// [`rustc_interface::interface::Config`] since the aformentioned type has really
@@ -14,11 +15,40 @@ use crate::util::get_sysroot;
 pub struct CompilerConfig {
     code_filename: String,
     code_input: String,
+    edition: Edition,
+    keep_thir: bool,
+    keep_borrowck_facts: bool,
 }
 
 impl CompilerConfig {
     pub fn new (filename: &str, input: &str) -> Self {
-        Self { code_filename: filename.into(), code_input: input.into() }
+        Self {
+            code_filename: filename.into(),
+            code_input: input.into(),
+            edition: DEFAULT_EDITION,
+            keep_thir: false,
+            keep_borrowck_facts: false,
+        }
+    }
+
+    /// Compile the code with the given edition instead of `rustc`'s default of 2015.
+    pub fn with_edition(mut self, edition: Edition) -> Self {
+        self.edition = edition;
+        self
+    }
+
+    /// Keep THIR and built MIR around with [`override_thir_queries`], for `Fn.thir`,
+    /// `Fn.mir(phase: "built")` and `Block.unsafe_operation`.
+    pub fn with_thir(mut self) -> Self {
+        self.keep_thir = true;
+        self
+    }
+
+    /// Keep borrowck facts around with [`override_queries`], for `Fn.borrowck`. This keeps
+    /// what [`with_thir`](Self::with_thir) does as well, and runs borrowck a second time for every body.
+    pub fn with_borrowck_facts(mut self) -> Self {
+        self.keep_borrowck_facts = true;
+        self
     }
 }
 
@@ -28,6 +58,9 @@ impl From<CompilerConfig> for Config {
         Config {
             opts: config::Options {
                 maybe_sysroot: Some(sysroot.into()),
+                edition: value.edition,
+                // Needed to answer which definitions intra-doc links point to.
+                resolve_doc_links: config::ResolveDocLinks::All,
                 ..config::Options::default()
             },
             input: config::Input::Str {
@@ -43,9 +76,15 @@ impl From<CompilerConfig> for Config {
             lint_caps: rustc_hash::FxHashMap::default(),
             parse_sess_created: None,
             register_lints: None,
-            override_queries: None,
+            override_queries: if value.keep_borrowck_facts {
+                Some(override_queries)
+            } else if value.keep_thir {
+                Some(override_thir_queries)
+            } else {
+                None
+            },
             make_codegen_backend: None,
-            registry: registry::Registry::new(&rustc_error_codes::DIAGNOSTICS),
+            registry: registry::Registry::new(rustc_error_codes::DIAGNOSTICS),
             expanded_args: Vec::new(),
             ice_file: None,
             hash_untracked_state: None,
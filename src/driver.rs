@@ -0,0 +1,218 @@
+//! A rustc driver that runs lint queries on the crate it compiles and reports what they find as
+//! diagnostics, so that they show up in `cargo build` and IDEs like rustc's own lints.
+//!
+//! The lints are [`Lint`]s, either those of the [catalog](crate::catalog) or ones loaded from
+//! `.gql` files with [`Lint::load`].
+
+use rustc_driver::{Callbacks, Compilation};
+use rustc_errors::{Diagnostic, Level};
+use rustc_interface::{interface::Compiler, Config, Queries};
+use rustc_session::lint;
+use trustfall::{execute_query, FieldValue};
+
+use crate::{
+    adapter::Adapter,
+    lints::{interpolate, Lint, Requirement, Severity},
+    query_overrides::{override_queries, override_thir_queries},
+};
+
+/// Runs [`Lint`]s once rustc has analyzed the crate, and emits a diagnostic at the level
+/// of the lint for every result, unless the crate is compiled with `--cap-lints allow`.
+/// `--cap-lints warn` turns errors into warnings.
+///
+/// Use it with [`rustc_driver::RunCompiler`]. It installs [`override_queries`](crate::override_queries)
+/// if a lint requires borrowck facts, or [`override_thir_queries`](crate::override_thir_queries) if
+/// one only requires THIR, so that builds whose lints need neither don't pay for keeping them.
+#[derive(Debug, Clone)]
+pub struct LintDriver {
+    lints: Vec<Lint>,
+}
+
+impl LintDriver {
+    pub fn new(lints: Vec<Lint>) -> Self {
+        Self { lints }
+    }
+}
+
+impl Callbacks for LintDriver {
+    fn config(&mut self, config: &mut Config) {
+        let requires = |requirement| self.lints.iter().any(|lint| lint.requires.contains(&requirement));
+        config.override_queries = if requires(Requirement::BorrowckFacts) {
+            Some(override_queries)
+        } else if requires(Requirement::Thir) {
+            Some(override_thir_queries)
+        } else {
+            None
+        };
+    }
+
+    fn after_analysis<'tcx>(&mut self, compiler: &Compiler, queries: &'tcx Queries<'tcx>) -> Compilation {
+        let sess = &compiler.sess;
+        let max_level = match sess.opts.lint_cap {
+            Some(lint::Level::Allow) => return Compilation::Continue,
+            Some(lint::Level::Warn) => Severity::Warning,
+            _ => Severity::Error,
+        };
+
+        let adapter = Adapter::new(queries);
+        for lint in &self.lints {
+            let results = match execute_query(Adapter::schema(), (&adapter).into(), &lint.query, lint.arguments.clone()) {
+                Ok(results) => results,
+                Err(error) => {
+                    sess.dcx().err(format!("the query of lint `{}` is invalid: {error}", lint.name));
+                    continue;
+                }
+            };
+            for outputs in results {
+                let level = match lint.level.min(max_level) {
+                    Severity::Note => Level::Note,
+                    Severity::Warning => Level::Warning(None),
+                    Severity::Error => Level::Error { lint: false },
+                };
+                let mut diagnostic = Diagnostic::new(level, interpolate(&lint.message, &outputs));
+                match outputs.get("span") {
+                    Some(FieldValue::String(span)) => match adapter.span(span) {
+                        Some(span) => { diagnostic.set_span(span); }
+                        None => { diagnostic.note(format!("found at {span}")); }
+                    },
+                    _ => { diagnostic.note("the query of the lint doesn't output a `span`"); }
+                }
+                diagnostic.note(format!("found by the lint query `{}`", lint.name));
+                sess.dcx().emit_diagnostic(diagnostic);
+            }
+        }
+        Compilation::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use rustc_driver::{Callbacks, Compilation, RunCompiler};
+    use rustc_interface::{interface::Compiler, Config, Queries};
+
+    use crate::{
+        lints::{Lint, Severity},
+        query_overrides::{override_queries, override_thir_queries},
+        util::get_sysroot,
+    };
+
+    use super::LintDriver;
+
+    /// Runs a [`LintDriver`], and records which providers it installed and how many errors there were.
+    struct Recorder {
+        driver: LintDriver,
+        override_queries: Option<usize>,
+        errors: usize,
+    }
+
+    impl Callbacks for Recorder {
+        fn config(&mut self, config: &mut Config) {
+            self.driver.config(config);
+            self.override_queries = config.override_queries.map(|providers| providers as usize);
+        }
+
+        fn after_analysis<'tcx>(&mut self, compiler: &Compiler, queries: &'tcx Queries<'tcx>) -> Compilation {
+            self.driver.after_analysis(compiler, queries);
+            self.errors = compiler.sess.dcx().err_count();
+            Compilation::Stop
+        }
+    }
+
+    fn run(lints: Vec<Lint>, args: &[&str]) -> (Option<usize>, usize) {
+        let path = env::temp_dir().join(format!("lint_driver_{}.rs", process::id()));
+        fs::write(&path, r#"pub fn first() {}
+
+fn second(x: Option<u32>) -> u32 { x.unwrap() }
+
+pub fn third(v: &mut Vec<u32>) { v.push(second(None)) }
+
+fn main() {}"#).unwrap();
+        let mut args: Vec<String> = ["rustc", path.to_str().unwrap(), "--sysroot", &get_sysroot()]
+            .into_iter()
+            .chain(args.iter().copied())
+            .map(String::from)
+            .collect();
+        args.push("--edition=2021".into());
+
+        let mut recorder = Recorder { driver: LintDriver::new(lints), override_queries: None, errors: 0 };
+        let _ = RunCompiler::new(&args, &mut recorder).run();
+        fs::remove_file(path).unwrap();
+        (recorder.override_queries, recorder.errors)
+    }
+
+    fn no_pub_fn() -> Lint {
+        Lint::parse("no_pub_fn", r#"# Functions other modules can call.
+# level: error
+# message: `{name}` is public
+# arguments: {"public": "public"}
+query {
+    Crate {
+        item {
+            ... on Fn {
+                visibility @filter(op: "=", value: ["$public"])
+                name: ident @output
+                span @output
+            }
+        }
+    }
+}"#).unwrap()
+    }
+
+    fn no_unwrap() -> Lint {
+        Lint::parse("no_unwrap", r#"# level: error
+# message: called `unwrap`
+# arguments: {"unwrap": "std::option::Option::<T>::unwrap"}
+# requires: thir
+query {
+    Crate {
+        fn {
+            thir {
+                expr {
+                    ... on ThirCall {
+                        span @output
+                        callee {
+                            path @filter(op: "=", value: ["$unwrap"])
+                        }
+                    }
+                }
+            }
+        }
+    }
+}"#).unwrap()
+    }
+
+    fn no_mut_borrow() -> Lint {
+        Lint::parse("no_mut_borrow", r#"# level: error
+# message: borrows `{place}` mutably
+# arguments: {"mut": "mut"}
+# requires: borrowck_facts
+query {
+    Crate {
+        fn {
+            borrowck {
+                borrow {
+                    kind @filter(op: "=", value: ["$mut"])
+                    place: borrowed_place @output
+                    span @output
+                }
+            }
+        }
+    }
+}"#).unwrap()
+    }
+
+    #[test]
+    fn lint_driver() {
+        let lint = no_pub_fn();
+        assert_eq!(lint.level, Severity::Error);
+        assert_eq!(lint.message, "`{name}` is public");
+        assert_eq!(lint.description, "Functions other modules can call.");
+
+        assert_eq!(run(vec![no_pub_fn()], &[]), (None, 2));
+        assert_eq!(run(vec![no_pub_fn(), no_unwrap()], &[]), (Some(override_thir_queries as usize), 3));
+        assert_eq!(run(vec![no_unwrap(), no_mut_borrow()], &[]), (Some(override_queries as usize), 2));
+        assert_eq!(run(vec![no_pub_fn(), no_unwrap(), no_mut_borrow()], &["--cap-lints", "warn"]).1, 0);
+    }
+}
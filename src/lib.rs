@@ -1,5 +1,6 @@
 #![feature(rustc_private)]
 
+extern crate rustc_ast;
 extern crate rustc_ast_pretty;
 extern crate rustc_driver;
 extern crate rustc_error_codes;
@@ -9,13 +10,27 @@ extern crate rustc_hir;
 extern crate rustc_interface;
 extern crate rustc_session;
 extern crate rustc_span;
+extern crate rustc_target;
 extern crate rustc_middle;
+extern crate rustc_mir_build;
 extern crate rustc_attr;
+extern crate rustc_borrowck;
+extern crate rustc_resolve;
+extern crate rustc_data_structures;
+extern crate pulldown_cmark;
 
 mod adapter;
 mod util;
 mod compiler_config;
+mod driver;
+mod lints;
+mod msrv;
+mod query_overrides;
 
-pub use adapter::{Adapter, Vertex};
+pub use adapter::{Adapter, AttrOrigin, MirOrigin, PanicKind, TyOrigin, Vertex};
 pub use util::get_sysroot;
 pub use compiler_config::CompilerConfig;
+pub use driver::LintDriver;
+pub use lints::{catalog, run_lints, Finding, Lint, LintError, Requirement, Severity};
+pub use msrv::{msrv, msrv_requirements, MsrvRequirement};
+pub use query_overrides::{override_queries, override_thir_queries};
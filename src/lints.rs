@@ -0,0 +1,431 @@
+//! Clippy-style lints written as Trustfall queries against the adapter's schema, and a runner
+//! that executes them against a crate.
+//!
+//! The [catalog] lives in the `queries/` directory of this repository. Every query outputs the
+//! `span` of what it found, next to whatever else explains the finding, and looks at the
+//! functions of the crate through `Crate.fn`, so that the functions of impls are linted too.
+//!
+//! A lint is a `.gql` file, configured with comments at the top of the file:
+//!
+//! ```graphql
+//! # Functions nobody should call.
+//! # level: warning
+//! # message: called `{callee}`
+//! # arguments: {"forbidden": ["std::process::exit"]}
+//! # requires: thir
+//! query {
+//!     ...
+//! }
+//! ```
+//!
+//! `level` is `note`, `warning` (the default) or `error`, `message` is what a finding says,
+//! with `{output}` standing for the value of an output of the query, and `arguments` holds the
+//! values of the `$variables` of the query as a JSON object. `requires` lists the
+//! [`Requirement`]s of the query by [name](Requirement::name), separated by commas. The other
+//! comments describe the lint.
+//!
+//! Clippy's `redundant_closure` and `large_enum_variant` aren't recreated: the schema has neither
+//! the bodies of closures nor the layouts of enum variants, which they'd have to look at.
+
+use std::{collections::BTreeMap, fmt, fs, io, path::Path, sync::{Arc, OnceLock}};
+
+use trustfall::{execute_query, FieldValue, TransparentValue};
+
+use crate::adapter::Adapter;
+
+/// How bad it is when a lint finds something, from least to most.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Worth a look, but often fine.
+    Note,
+    /// Likely a mistake or needlessly slow, but not wrong.
+    Warning,
+    /// Code that is almost certainly wrong.
+    Error,
+}
+
+/// Something a lint's query relies on that not every compilation provides.
+/// Without it, the lint doesn't fail, it just doesn't find anything.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Requirement {
+    /// `Fn.thir` or `Fn.mir(phase: "built")`, which need the compilation to be configured with
+    /// [`override_thir_queries`](crate::override_thir_queries) or [`override_queries`](crate::override_queries).
+    Thir,
+    /// `Fn.borrowck`, which needs the compilation to be configured with
+    /// [`override_queries`](crate::override_queries).
+    BorrowckFacts,
+    /// Queries of optimized MIR like `Fn.may_panic`, which rustc only builds for crates
+    /// without errors.
+    OptimizedMir,
+}
+
+impl Requirement {
+    /// Whether the compilation has to be configured with [`override_queries`](crate::override_queries)
+    /// or [`override_thir_queries`](crate::override_thir_queries) for lints with this requirement
+    /// to find anything.
+    pub fn needs_override_queries(self) -> bool {
+        match self {
+            Self::Thir | Self::BorrowckFacts => true,
+            Self::OptimizedMir => false,
+        }
+    }
+
+    /// The name of the requirement in the `requires` comment of a lint's `.gql` file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Thir => "thir",
+            Self::BorrowckFacts => "borrowck_facts",
+            Self::OptimizedMir => "optimized_mir",
+        }
+    }
+}
+
+/// A lint: a Trustfall query that outputs the `span` of what it found, with what to tell about
+/// it. The lints of the [catalog] and ones loaded with [`Lint::load`] are read from `.gql` files,
+/// see the [module docs](self) for their format.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct Lint {
+    /// The name of the lint, the same as the Clippy lint it recreates if there is one.
+    pub name: String,
+    /// What the lint finds and why that's a problem.
+    pub description: String,
+    /// What to tell about a finding, with `{output}` standing for the value of an output of
+    /// the query, see [`Finding::message`].
+    pub message: String,
+    /// How severe a finding is, set by a `# level: note`, `warning` or `error` header.
+    /// [`Severity::Warning`] without one.
+    pub level: Severity,
+    /// What the query relies on, see [`Requirement`].
+    pub requires: Vec<Requirement>,
+    /// The Trustfall query, which outputs a `span`.
+    pub query: String,
+    /// The values of the `$variables` of the query.
+    pub arguments: BTreeMap<Arc<str>, FieldValue>,
+}
+
+impl Lint {
+    /// Reads a lint from a `.gql` file. The lint is named after the file.
+    pub fn load(path: &Path) -> Result<Self, LintError> {
+        let source = fs::read_to_string(path).map_err(LintError::Io)?;
+        let name = path.file_stem().unwrap_or(path.as_os_str()).to_string_lossy();
+        Self::parse(&name, &source)
+    }
+
+    /// Reads a lint named `name` from the contents of a `.gql` file.
+    pub fn parse(name: &str, source: &str) -> Result<Self, LintError> {
+        let mut lint = Self {
+            name: name.into(),
+            description: String::new(),
+            message: format!("found by `{name}`"),
+            level: Severity::Warning,
+            requires: vec![],
+            query: source.into(),
+            arguments: BTreeMap::new(),
+        };
+
+        let comments = source
+            .lines()
+            .map(str::trim)
+            .take_while(|line| line.is_empty() || line.starts_with('#'))
+            .filter_map(|line| line.strip_prefix('#'))
+            .map(str::trim);
+        let mut description = vec![];
+        for comment in comments {
+            let (key, value) = comment.split_once(':').unwrap_or((comment, ""));
+            let value = value.trim();
+            match key {
+                "level" => lint.level = match value {
+                    "note" => Severity::Note,
+                    "warning" => Severity::Warning,
+                    "error" => Severity::Error,
+                    _ => return Err(LintError::UnknownLevel(value.into())),
+                },
+                "message" => lint.message = value.into(),
+                "arguments" => {
+                    let arguments: BTreeMap<Arc<str>, TransparentValue> = serde_json::from_str(value)
+                        .map_err(LintError::InvalidArguments)?;
+                    lint.arguments = arguments.into_iter().map(|(name, value)| (name, value.into())).collect();
+                }
+                "requires" => {
+                    let requirements = [Requirement::Thir, Requirement::BorrowckFacts, Requirement::OptimizedMir];
+                    lint.requires = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .map(|name| {
+                            requirements
+                                .into_iter()
+                                .find(|requirement| requirement.name() == name)
+                                .ok_or_else(|| LintError::UnknownRequirement(name.into()))
+                        })
+                        .collect::<Result<_, _>>()?;
+                }
+                _ => description.push(comment),
+            }
+        }
+        lint.description = description.join(" ");
+        Ok(lint)
+    }
+}
+
+/// Why a [`Lint`] couldn't be loaded or run.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum LintError {
+    Io(io::Error),
+    /// The `level` comment names none of `note`, `warning` and `error`.
+    UnknownLevel(String),
+    /// The `arguments` comment isn't a JSON object of argument values.
+    InvalidArguments(serde_json::Error),
+    /// The `requires` comment names something that isn't a [`Requirement`].
+    UnknownRequirement(String),
+    /// The query of the lint named `lint` doesn't fit the schema or its arguments.
+    InvalidQuery { lint: String, message: String },
+    /// A result of the query of the lint named `lint` has no `span` output.
+    MissingSpan { lint: String },
+}
+
+impl fmt::Display for LintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::UnknownLevel(level) => write!(f, "unknown level `{level}`, expected `note`, `warning` or `error`"),
+            Self::InvalidArguments(error) => write!(f, "invalid arguments: {error}"),
+            Self::UnknownRequirement(name) => write!(f, "unknown requirement `{name}`"),
+            Self::InvalidQuery { lint, message } => write!(f, "the query of lint `{lint}` is invalid: {message}"),
+            Self::MissingSpan { lint } => write!(f, "the query of lint `{lint}` doesn't output a `span`"),
+        }
+    }
+}
+
+impl std::error::Error for LintError {}
+
+/// Something a lint found.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct Finding<'a> {
+    pub lint: &'a Lint,
+    /// Where the lint found something, as `file:line:col: line:col`.
+    pub span: String,
+    /// The other outputs of the lint's query.
+    pub outputs: BTreeMap<Arc<str>, FieldValue>,
+}
+
+impl Finding<'_> {
+    /// The [`message`](Lint::message) of the lint, with the outputs filled in.
+    pub fn message(&self) -> String {
+        interpolate(&self.lint.message, &self.outputs)
+    }
+}
+
+/// Replaces every `{name}` in `template` with the value of the output `name`.
+/// Strings are inserted as they are, lists as their elements separated by commas, and
+/// placeholders that aren't outputs are left alone.
+pub(crate) fn interpolate(template: &str, outputs: &BTreeMap<Arc<str>, FieldValue>) -> String {
+    fn write_value(message: &mut String, value: &FieldValue) {
+        match value {
+            FieldValue::Null => message.push_str("null"),
+            FieldValue::Int64(value) => message.push_str(&value.to_string()),
+            FieldValue::Uint64(value) => message.push_str(&value.to_string()),
+            FieldValue::Float64(value) => message.push_str(&value.to_string()),
+            FieldValue::String(value) | FieldValue::Enum(value) => message.push_str(value),
+            FieldValue::Boolean(value) => message.push_str(&value.to_string()),
+            FieldValue::List(values) => for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    message.push_str(", ");
+                }
+                write_value(message, value);
+            },
+            _ => message.push_str(&format!("{value:?}")),
+        }
+    }
+
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        message.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = rest.find('}').and_then(|end| Some((end, outputs.get(&rest[1..end])?)));
+        match value {
+            Some((end, value)) => {
+                write_value(&mut message, value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+/// The `.gql` files of the catalog, by lint name.
+const CATALOG: &[(&str, &str)] = &[
+    ("clone_on_copy", include_str!("../queries/clone_on_copy.gql")),
+    ("manual_map", include_str!("../queries/manual_map.gql")),
+    ("missing_panics_doc", include_str!("../queries/missing_panics_doc.gql")),
+    ("missing_safety_doc", include_str!("../queries/missing_safety_doc.gql")),
+    ("needless_collect", include_str!("../queries/needless_collect.gql")),
+    ("unwrap_used", include_str!("../queries/unwrap_used.gql")),
+];
+
+static LINTS: OnceLock<Vec<Lint>> = OnceLock::new();
+
+/// Every lint this crate ships, read from the `.gql` files in `queries/`.
+pub fn catalog() -> &'static [Lint] {
+    LINTS.get_or_init(|| {
+        CATALOG
+            .iter()
+            .map(|(name, source)| Lint::parse(name, source).expect("lints of the catalog should be valid"))
+            .collect()
+    })
+}
+
+/// Runs `lints` against the crate `adapter` queries, and returns what they found,
+/// lint by lint, in the order the queries return their results.
+pub fn run_lints<'a>(adapter: &Adapter<'_>, lints: &'a [Lint]) -> Result<Vec<Finding<'a>>, LintError> {
+    let mut findings = vec![];
+    for lint in lints {
+        let results = execute_query(Adapter::schema(), adapter.into(), &lint.query, lint.arguments.clone())
+            .map_err(|error| LintError::InvalidQuery { lint: lint.name.clone(), message: error.to_string() })?;
+        for mut outputs in results {
+            let span = match outputs.remove("span") {
+                Some(FieldValue::String(span)) => span.to_string(),
+                _ => return Err(LintError::MissingSpan { lint: lint.name.clone() }),
+            };
+            findings.push(Finding { lint, span, outputs });
+        }
+    }
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rustc_interface::run_compiler;
+
+    use crate::{adapter::Adapter, compiler_config::CompilerConfig};
+
+    use super::{catalog, run_lints, Lint, LintError, Requirement, Severity};
+
+    #[test]
+    fn catalog_findings() {
+        let config = CompilerConfig::new("main.rs", r#"#[derive(Clone, Copy)]
+pub struct Point(i32, i32);
+
+pub fn even_count(values: &[i32]) -> usize {
+    values.iter().filter(|value| **value % 2 == 0).collect::<Vec<_>>().len()
+}
+
+pub fn double(value: Option<u32>) -> Option<u32> {
+    match value {
+        Some(value) => Some(value.wrapping_mul(2)),
+        None => None,
+    }
+}
+
+pub fn positive(value: Option<u32>) -> Option<u32> {
+    match value {
+        Some(value) => if value > 0 { Some(value) } else { None },
+        None => None,
+    }
+}
+
+/// Parses `text`.
+///
+/// # Panics
+///
+/// If `text` isn't a number.
+pub fn parse(text: &str) -> i32 {
+    text.parse().unwrap()
+}
+
+pub fn first(values: &[i32]) -> i32 {
+    values[0]
+}
+
+pub unsafe fn read(pointer: *const u8) -> u8 {
+    *pointer
+}
+
+fn copy(point: &Point) -> Point {
+    point.clone()
+}
+
+fn main() {
+    copy(&Point(1, 2));
+}
+
+fn wrap(values: Vec<i32>) -> Vec<i32> {
+    values
+}
+
+pub fn wrapped_count(values: &[i32]) -> usize {
+    wrap(values.iter().copied().collect::<Vec<_>>()).len()
+}
+
+impl Point {
+    pub fn from_x(x: Option<i32>) -> Point {
+        Point(x.unwrap(), 0)
+    }
+
+    pub unsafe fn from_raw(pointer: *const u8) -> Point {
+        Point(i32::from(*pointer), 0)
+    }
+}"#).with_thir();
+        run_compiler(config.into(), |compiler| {
+            compiler.enter(|queries| {
+                let adapter = Adapter::new(queries);
+                let findings = run_lints(&adapter, catalog())
+                    .unwrap()
+                    .into_iter()
+                    .map(|finding| (finding.lint.name.as_str(), finding.message(), finding.span))
+                    .collect_vec();
+                assert_eq!(findings, vec![
+                    ("clone_on_copy", "using `clone` on `Point`, which is `Copy`".to_string(), "<main.rs>:40:5: 40:18".to_string()),
+                    ("manual_map", "manual implementation of `Option::map`".to_string(), "<main.rs>:9:5: 12:6".to_string()),
+                    ("missing_panics_doc", "docs of `first`, which may panic, have no `# Panics` section".to_string(), "<main.rs>:31:1: 31:36".to_string()),
+                    ("missing_panics_doc", "docs of `from_x`, which may panic, have no `# Panics` section".to_string(), "<main.rs>:56:5: 56:43".to_string()),
+                    ("missing_safety_doc", "docs of unsafe function `read` have no `# Safety` section".to_string(), "<main.rs>:35:1: 35:45".to_string()),
+                    ("missing_safety_doc", "docs of unsafe function `from_raw` have no `# Safety` section".to_string(), "<main.rs>:60:5: 60:56".to_string()),
+                    ("needless_collect", "collecting into `std::vec::Vec<&i32>` only to call `std::vec::Vec::<T, A>::len` on it".to_string(), "<main.rs>:5:5: 5:77".to_string()),
+                    ("unwrap_used", "called `std::result::Result::<T, E>::unwrap`".to_string(), "<main.rs>:28:5: 28:26".to_string()),
+                    ("unwrap_used", "called `std::option::Option::<T>::unwrap`".to_string(), "<main.rs>:57:15: 57:25".to_string()),
+                ]);
+            })
+        });
+    }
+
+    #[test]
+    fn parse() {
+        let lint = Lint::parse("borrows", r#"# Borrows
+# of locals.
+# level: error
+# arguments: {"zero": 0}
+# requires: thir, borrowck_facts
+query { Crate { name @output } }"#).unwrap();
+        assert_eq!(lint.description, "Borrows of locals.");
+        assert_eq!(lint.level, Severity::Error);
+        assert_eq!(lint.message, "found by `borrows`");
+        assert_eq!(lint.arguments["zero"], 0.into());
+        assert_eq!(lint.requires, vec![Requirement::Thir, Requirement::BorrowckFacts]);
+
+        let error = Lint::parse("borrows", "# requires: hir\nquery { Crate { name @output } }").unwrap_err();
+        assert!(matches!(error, LintError::UnknownRequirement(name) if name == "hir"));
+
+        let config = CompilerConfig::new("main.rs", "fn main() {}");
+        run_compiler(config.into(), |compiler| {
+            compiler.enter(|queries| {
+                let adapter = Adapter::new(queries);
+                let lint = Lint::parse("crate_name", "query { Crate { name @output } }").unwrap();
+                let error = run_lints(&adapter, &[lint]).unwrap_err();
+                assert!(matches!(error, LintError::MissingSpan { lint } if lint == "crate_name"));
+            })
+        });
+    }
+}
@@ -0,0 +1,147 @@
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_span;
+
+use std::{env, fs, path::{Path, PathBuf}, process::ExitCode};
+
+use rustc_driver::{catch_with_exit_code, RunCompiler};
+use rustc_interface::run_compiler;
+use rustc_span::edition::Edition;
+use trustfall_rustc_adapter::{
+    catalog, msrv, msrv_requirements, CompilerConfig, Lint, LintDriver,
+};
+
+const USAGE: &str = "usage: trustfall-rustc-adapter msrv [--edition <edition>] [--all] <file.rs>
+       RUSTC_WORKSPACE_WRAPPER=trustfall-rustc-adapter cargo build";
+
+/// Where the lints come from when the binary wraps rustc: `.gql` files and directories of them,
+/// separated like the paths of `PATH`. Without it, the lints of the catalog run.
+const LINTS_VAR: &str = "TRUSTFALL_LINTS";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.split_first() {
+        Some((subcommand, args)) if subcommand == "msrv" => msrv_command(args),
+        // Cargo runs `RUSTC_WORKSPACE_WRAPPER` with the path of rustc as the first argument.
+        Some((rustc, args)) if Path::new(rustc).file_stem().is_some_and(|stem| stem == "rustc") => {
+            rustc_command(rustc, args)
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Prints the MSRV of a single-file crate and what forces it, or with `--all`
+/// every requirement found.
+fn msrv_command(args: &[String]) -> ExitCode {
+    let mut edition = Edition::Edition2015;
+    let mut all = false;
+    let mut file = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--edition" => match args.next().map(|edition| edition.parse()) {
+                Some(Ok(parsed)) => edition = parsed,
+                _ => {
+                    eprintln!("{USAGE}");
+                    return ExitCode::FAILURE;
+                }
+            },
+            "--all" => all = true,
+            _ if file.is_none() => file = Some(arg),
+            _ => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    let Some(file) = file else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+    let input = match fs::read_to_string(file) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("error: couldn't read {file}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let config = CompilerConfig::new(file, &input).with_edition(edition);
+    run_compiler(config.into(), |compiler| {
+        compiler.enter(|queries| {
+            let Ok(mut gcx) = queries.global_ctxt() else {
+                return ExitCode::FAILURE;
+            };
+            gcx.enter(|tcx| {
+                if tcx.analysis(()).is_err() {
+                    return ExitCode::FAILURE;
+                }
+                let source_map = tcx.sess.source_map();
+                let requirements = if all {
+                    msrv_requirements(tcx)
+                } else {
+                    msrv(tcx).into_iter().collect()
+                };
+                if requirements.is_empty() {
+                    println!("1.0.0");
+                }
+                for requirement in requirements {
+                    println!(
+                        "{} {}: {}",
+                        requirement.version,
+                        source_map.span_to_diagnostic_string(requirement.span),
+                        requirement.reason,
+                    );
+                }
+                ExitCode::SUCCESS
+            })
+        })
+    })
+}
+
+/// Compiles a crate like rustc would, and reports what the lints find as diagnostics.
+fn rustc_command(rustc: &str, args: &[String]) -> ExitCode {
+    let lints = match env::var_os(LINTS_VAR) {
+        Some(paths) => {
+            let mut lints = vec![];
+            for path in env::split_paths(&paths) {
+                if let Err(error) = load_lints(&path, &mut lints) {
+                    eprintln!("error: couldn't load the lints in {}: {error}", path.display());
+                    return ExitCode::FAILURE;
+                }
+            }
+            lints
+        }
+        None => catalog().to_vec(),
+    };
+
+    let args: Vec<String> = std::iter::once(rustc.into()).chain(args.iter().cloned()).collect();
+    // No `--sysroot` is added: rustc then loads std from the toolchain of the `rustc_driver` this
+    // binary runs on, while the wrapped rustc may be of another one, whose metadata it can't read.
+    let mut driver = LintDriver::new(lints);
+    let code = catch_with_exit_code(|| RunCompiler::new(&args, &mut driver).run());
+    ExitCode::from(u8::try_from(code).unwrap_or(1))
+}
+
+/// Adds the lint in `path`, or the lints of the `.gql` files in it if it's a directory, to `lints`.
+fn load_lints(path: &Path, lints: &mut Vec<Lint>) -> Result<(), Box<dyn std::error::Error>> {
+    if path.is_dir() {
+        let mut files: Vec<PathBuf> = fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<_, _>>()?;
+        files.retain(|file| file.extension().is_some_and(|extension| extension == "gql"));
+        files.sort();
+        for file in files {
+            lints.push(Lint::load(&file)?);
+        }
+    } else {
+        lints.push(Lint::load(path)?);
+    }
+    Ok(())
+}
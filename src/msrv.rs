@@ -0,0 +1,366 @@
+//! Finds the oldest Rust release a crate compiles with.
+//!
+//! [`msrv_requirements`] walks the bodies of the crate for what the toolchain only supports since
+//! some release: `std`, `core` and `alloc` definitions that are referred to, called or used through
+//! an impl, `std` macros, const calls in const contexts, the edition and some syntax. [`msrv`]
+//! picks the requirement with the highest release.
+//!
+//! It only sees code that is compiled: code that's `cfg`'d out isn't checked, and neither is what
+//! macros of other crates expand to, since the requirements of those are for that crate to keep.
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::{
+    def::{DefKind, Res},
+    def_id::{DefId, LocalDefId, CRATE_DEF_ID},
+    intravisit::{walk_expr, walk_local, walk_path, Visitor},
+    BodyId, Closure, ClosureKind, CoroutineDesugaring, CoroutineKind, Expr, ExprKind, Local,
+    MatchSource, Path, QPath, HirId,
+};
+use rustc_middle::{hir::nested_filter::All, lint::in_external_macro, ty::{Instance, TyCtxt, TypeckResults}};
+use rustc_session::RustcVersion;
+use rustc_span::{edition::Edition, hygiene::{ExpnId, ExpnKind, MacroKind}, sym, Span};
+
+use crate::util::stable_since;
+
+/// Something in a crate that needs at least a certain Rust release to compile.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct MsrvRequirement {
+    /// The first Rust release that supports what is required.
+    pub version: RustcVersion,
+    /// Where the requirement comes from.
+    pub span: Span,
+    /// What is required, e.g. "`core::str::<impl str>::split_once` is stable since 1.52.0".
+    pub reason: String,
+}
+
+/// The requirement that forces the highest Rust release on the crate, or `None`
+/// if nothing the analysis knows about needs more than Rust 1.0.0.
+///
+/// See [`msrv_requirements`] for what is taken into account.
+pub fn msrv(tcx: TyCtxt<'_>) -> Option<MsrvRequirement> {
+    msrv_requirements(tcx)
+        .into_iter()
+        .reduce(|max, requirement| if requirement.version > max.version { requirement } else { max })
+}
+
+/// Every requirement on the Rust release found in the crate, in source order:
+/// the `since` version of every stable `std`, `core` and `alloc` definition that is
+/// referred to by a path, called as a method, used through a trait impl or invoked as a
+/// macro, the const stability of functions called in const contexts, the edition and
+/// some syntax like `let`-`else`.
+pub fn msrv_requirements(tcx: TyCtxt<'_>) -> Vec<MsrvRequirement> {
+    let mut visitor = MsrvVisitor {
+        tcx,
+        typeck_results: None,
+        body_owner: None,
+        in_const_context: false,
+        seen_expansions: FxHashSet::default(),
+        requirements: Vec::new(),
+    };
+
+    let edition_since = match tcx.sess.edition() {
+        Edition::Edition2015 => None,
+        Edition::Edition2018 => Some(RustcVersion { major: 1, minor: 31, patch: 0 }),
+        Edition::Edition2021 => Some(RustcVersion { major: 1, minor: 56, patch: 0 }),
+        // Not stable yet, so no release supports it.
+        Edition::Edition2024 => None,
+    };
+    if let Some(version) = edition_since {
+        visitor.requirements.push(MsrvRequirement {
+            version,
+            span: tcx.def_span(CRATE_DEF_ID),
+            reason: format!("edition {} is stable since {version}", tcx.sess.edition()),
+        });
+    }
+
+    tcx.hir().walk_toplevel_module(&mut visitor);
+    visitor.requirements
+}
+
+struct MsrvVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    typeck_results: Option<&'tcx TypeckResults<'tcx>>,
+    body_owner: Option<LocalDefId>,
+    in_const_context: bool,
+    seen_expansions: FxHashSet<ExpnId>,
+    requirements: Vec<MsrvRequirement>,
+}
+
+impl<'tcx> MsrvVisitor<'tcx> {
+    fn require(&mut self, version: RustcVersion, span: Span, reason: String) {
+        self.requirements.push(MsrvRequirement { version, span, reason });
+    }
+
+    fn require_syntax(&mut self, span: Span, syntax: &str, (major, minor): (u16, u16)) {
+        let version = RustcVersion { major, minor, patch: 0 };
+        self.require(version, span, format!("{syntax} is stable since {version}"));
+    }
+
+    /// Whether `def_id` comes with the toolchain, so that its stability says
+    /// which release it was added in.
+    fn is_from_std(&self, def_id: DefId) -> bool {
+        !def_id.is_local()
+            && matches!(self.tcx.crate_name(def_id.krate), sym::std | sym::core | sym::alloc)
+    }
+
+    /// Whether `span` was written by hand, and not by an external macro or a desugaring
+    /// whose own requirements are already accounted for.
+    fn is_user_written(&self, span: Span) -> bool {
+        !in_external_macro(self.tcx.sess, span) && span.desugaring_kind().is_none()
+    }
+
+    fn require_def(&mut self, def_id: DefId, span: Span) {
+        if !self.is_from_std(def_id) || !self.is_user_written(span) {
+            return;
+        }
+        // Constructors don't have a stability of their own.
+        let def_id = match self.tcx.def_kind(def_id) {
+            DefKind::Ctor(..) => self.tcx.parent(def_id),
+            _ => def_id,
+        };
+        let Some(stability) = self.tcx.lookup_stability(def_id) else {
+            return;
+        };
+        if let Some(version) = stable_since(stability.level) {
+            let path = self.tcx.def_path_str(def_id);
+            self.require(version, span, format!("`{path}` is stable since {version}"));
+        }
+    }
+
+    fn require_const_def(&mut self, def_id: DefId, span: Span) {
+        if !self.is_from_std(def_id) || !self.is_user_written(span) {
+            return;
+        }
+        let Some(stability) = self.tcx.lookup_const_stability(def_id) else {
+            return;
+        };
+        if let Some(version) = stable_since(stability.level) {
+            let path = self.tcx.def_path_str(def_id);
+            self.require(version, span, format!("`{path}` is const-stable since {version}"));
+        }
+    }
+
+    /// Requires the impl a call to a trait method resolves to, e.g. the
+    /// `impl From<[T; N]> for Vec<T>` behind `Vec::from([1, 2])` or the
+    /// `impl Div<NonZeroU32> for u32` behind `10u32 / NonZeroU32::new(2).unwrap()`.
+    fn require_trait_impl(&mut self, hir_id: HirId, def_id: DefId, span: Span) {
+        let (Some(typeck_results), Some(body_owner)) = (self.typeck_results, self.body_owner) else {
+            return;
+        };
+        if self.tcx.trait_of_item(def_id).is_none() || !self.is_user_written(span) {
+            return;
+        }
+        let args = self.tcx.erase_regions(typeck_results.node_args(hir_id));
+        let param_env = self.tcx.param_env(body_owner);
+        let Ok(Some(instance)) = Instance::resolve(self.tcx, param_env, def_id, args) else {
+            return;
+        };
+        let Some(impl_def_id) = self.tcx.impl_of_method(instance.def_id()) else {
+            return;
+        };
+        if !self.is_from_std(impl_def_id) {
+            return;
+        }
+        if let Some(version) = self.tcx.lookup_stability(impl_def_id).and_then(|stability| stable_since(stability.level)) {
+            let path = self.tcx.def_path_str(impl_def_id);
+            self.require(version, span, format!("`{path}` is stable since {version}"));
+        }
+    }
+
+    /// Requires every `std` macro `span` was expanded from. Built-in derives and attributes
+    /// are left out, since their macros are only marked stable since they were added to
+    /// the prelude in 1.38.0.
+    fn require_macros(&mut self, span: Span) {
+        let mut ctxt = span.ctxt();
+        while !ctxt.is_root() {
+            let expn_id = ctxt.outer_expn();
+            let expn_data = expn_id.expn_data();
+            if self.seen_expansions.insert(expn_id) {
+                if let (ExpnKind::Macro(MacroKind::Bang, _), Some(def_id)) = (expn_data.kind, expn_data.macro_def_id) {
+                    self.require_def(def_id, expn_data.call_site);
+                }
+            }
+            ctxt = expn_data.call_site.ctxt();
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for MsrvVisitor<'tcx> {
+    type NestedFilter = All;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_nested_body(&mut self, body_id: BodyId) {
+        let hir = self.tcx.hir();
+        let body_owner = hir.body_owner_def_id(body_id);
+        let outer = (self.typeck_results, self.body_owner, self.in_const_context);
+        self.typeck_results = Some(self.tcx.typeck_body(body_id));
+        self.body_owner = Some(body_owner);
+        self.in_const_context = hir.body_const_context(body_owner).is_some();
+        self.visit_body(hir.body(body_id));
+        (self.typeck_results, self.body_owner, self.in_const_context) = outer;
+    }
+
+    fn visit_path(&mut self, path: &Path<'tcx>, _id: HirId) {
+        for segment in path.segments {
+            if let Res::Def(_, def_id) = segment.res {
+                self.require_def(def_id, segment.ident.span);
+            }
+        }
+        walk_path(self, path);
+    }
+
+    fn visit_local(&mut self, local: &'tcx Local<'tcx>) {
+        self.require_macros(local.span);
+        if local.els.is_some() && self.is_user_written(local.span) {
+            self.require_syntax(local.span, "`let`-`else`", (1, 65));
+        }
+        walk_local(self, local);
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        self.require_macros(expr.span);
+
+        if let Some(typeck_results) = self.typeck_results {
+            // The definition a method call, overloaded operator or path expression refers to,
+            // and where it's named. Only typeck knows what method calls, operators and
+            // type-relative paths like `Vec::new` refer to.
+            let referred = match expr.kind {
+                ExprKind::MethodCall(segment, ..) | ExprKind::Path(QPath::TypeRelative(_, segment)) => {
+                    typeck_results
+                        .type_dependent_def_id(expr.hir_id)
+                        .map(|def_id| (def_id, segment.ident.span))
+                }
+                ExprKind::Binary(op, ..) | ExprKind::AssignOp(op, ..) => {
+                    typeck_results
+                        .type_dependent_def_id(expr.hir_id)
+                        .map(|def_id| (def_id, op.span))
+                }
+                ExprKind::Index(_, _, brackets_span) => {
+                    typeck_results
+                        .type_dependent_def_id(expr.hir_id)
+                        .map(|def_id| (def_id, brackets_span))
+                }
+                ExprKind::Unary(..) => {
+                    typeck_results
+                        .type_dependent_def_id(expr.hir_id)
+                        .map(|def_id| (def_id, expr.span))
+                }
+                ExprKind::Path(ref qpath @ QPath::Resolved(..)) => {
+                    typeck_results
+                        .qpath_res(qpath, expr.hir_id)
+                        .opt_def_id()
+                        .map(|def_id| (def_id, expr.span))
+                }
+                _ => None,
+            };
+            if let Some((def_id, span)) = referred {
+                // Resolved paths are already required by `visit_path`.
+                if !matches!(expr.kind, ExprKind::Path(QPath::Resolved(..))) {
+                    self.require_def(def_id, span);
+                }
+                self.require_trait_impl(expr.hir_id, def_id, span);
+                if self.in_const_context {
+                    self.require_const_def(def_id, span);
+                }
+            }
+        }
+
+        match expr.kind {
+            ExprKind::Match(_, _, MatchSource::TryDesugar(_)) => {
+                self.require_syntax(expr.span, "the `?` operator", (1, 13));
+            }
+            ExprKind::Closure(Closure {
+                kind: ClosureKind::Coroutine(CoroutineKind::Desugared(CoroutineDesugaring::Async, _)),
+                ..
+            }) => {
+                self.require_syntax(expr.span, "`async`", (1, 39));
+            }
+            _ => {}
+        }
+
+        walk_expr(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustc_interface::run_compiler;
+    use rustc_session::RustcVersion;
+    use rustc_span::edition::Edition;
+
+    use crate::compiler_config::CompilerConfig;
+
+    use super::{msrv, msrv_requirements};
+
+    #[test]
+    fn highest_requirement() {
+        let config = CompilerConfig::new("main.rs", r#"fn main() {
+    let x = Some(42);
+    x.is_some();
+    x.is_some_and(|x| x > 0);
+    let _ = "a,b".split_once(',');
+    let _ = Vec::from([1, 2]);
+    println!("{}", matches!(x, Some(_)));
+}"#).with_edition(Edition::Edition2021);
+        run_compiler(config.into(), |compiler| {
+            compiler.enter(|queries| {
+                queries.global_ctxt().unwrap().enter(|tcx| {
+                    let msrv = msrv(tcx).unwrap();
+                    assert_eq!(msrv.version, RustcVersion { major: 1, minor: 70, patch: 0 });
+                    assert_eq!(msrv.reason, "`std::option::Option::<T>::is_some_and` is stable since 1.70.0");
+                })
+            })
+        });
+    }
+
+    #[test]
+    fn syntax_and_const_requirements() {
+        let config = CompilerConfig::new("main.rs", r#"const ONES: u32 = u32::MAX.count_ones();
+fn main() {
+    let Some(x) = Some(ONES) else { return };
+    let _ = x.count_ones();
+}"#);
+        run_compiler(config.into(), |compiler| {
+            compiler.enter(|queries| {
+                queries.global_ctxt().unwrap().enter(|tcx| {
+                    let reasons: Vec<_> = msrv_requirements(tcx)
+                        .into_iter()
+                        .filter(|requirement| requirement.version > RustcVersion { major: 1, minor: 0, patch: 0 })
+                        .map(|requirement| requirement.reason)
+                        .collect();
+                    assert_eq!(reasons, vec![
+                        "`core::num::<impl u32>::count_ones` is const-stable since 1.32.0",
+                        "`core::num::<impl u32>::MAX` is stable since 1.43.0",
+                        "`let`-`else` is stable since 1.65.0",
+                    ]);
+                })
+            })
+        });
+    }
+
+    #[test]
+    fn overloaded_operator_impls() {
+        let config = CompilerConfig::new("main.rs", r#"use std::num::NonZeroU32;
+fn main() {
+    let two = NonZeroU32::new(2).unwrap();
+    let _ = 10u32 / two;
+    let _ = 10u32 / 2;
+}"#);
+        run_compiler(config.into(), |compiler| {
+            compiler.enter(|queries| {
+                queries.global_ctxt().unwrap().enter(|tcx| {
+                    let msrv = msrv(tcx).unwrap();
+                    assert_eq!(msrv.version, RustcVersion { major: 1, minor: 51, patch: 0 });
+                    assert_eq!(
+                        msrv.reason,
+                        "`core::num::nonzero::<impl std::ops::Div<std::num::NonZeroU32> for u32>` is stable since 1.51.0",
+                    );
+                })
+            })
+        });
+    }
+}
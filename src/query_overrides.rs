@@ -0,0 +1,228 @@
+//! Query providers that keep results around which rustc otherwise steals.
+//!
+//! Some queries hand out their result in a [`Steal`] so that the next step of the pipeline can
+//! take it over instead of cloning it, e.g. THIR is consumed when MIR is built from it, and that
+//! MIR is turned into the MIR borrowck runs on in place. The providers here copy those results
+//! before that can happen.
+//!
+//! Borrowck is different: what it finds out is thrown away once it's done, so its provider runs
+//! it a second time to get at it, as [`rustc_borrowck::consumers`] recommends. That can't wait
+//! until the facts of a body are asked for, since the MIR borrowck runs on is stolen by then, so
+//! it's done for every body, and only by [`override_queries`].
+//!
+//! Copying isn't free, so the providers are opt-in: [`override_thir_queries`] only keeps THIR and
+//! built MIR, and compilations that install neither don't pay for any of it.
+//!
+//! What's kept belongs to the session that installed the providers: it's only read back for a
+//! session whose queries use them, and only if that's the session it was kept for.
+
+use std::{cell::RefCell, ptr, rc::Rc};
+
+use rustc_borrowck::consumers::{get_body_with_borrowck_facts, BodyWithBorrowckFacts, ConsumerOptions};
+use rustc_data_structures::{fx::FxHashMap, steal::Steal, sync::{Lrc, Weak}};
+use rustc_middle::{mir::{Body, BorrowCheckResult}, query, thir::{ExprId, Thir}, ty::TyCtxt, util::Providers};
+use rustc_session::Session;
+use rustc_span::{def_id::LocalDefId, source_map::SourceMap, ErrorGuaranteed};
+
+thread_local! {
+    /// What the providers kept, for the session that installed them last on this thread.
+    static KEPT: RefCell<Option<Kept>> = Default::default();
+}
+
+/// The results kept during one compilation session. They refer to its arenas, so they're only
+/// handed out to that session, and dropped once it's over.
+struct Kept {
+    /// Which session the results belong to. The weak reference keeps the allocation of its
+    /// source map from being reused by a later session while this is around.
+    session: Weak<SourceMap>,
+    built_mir: FxHashMap<LocalDefId, &'static Body<'static>>,
+    thir: FxHashMap<LocalDefId, (&'static Steal<Thir<'static>>, ExprId)>,
+    borrowck_facts: FxHashMap<LocalDefId, Rc<BodyWithBorrowckFacts<'static>>>,
+}
+
+/// Calls `f` with what was kept for the session of `ctxt`. `None` if nothing was, in which case
+/// what an earlier session kept is dropped if that session is over.
+fn with_kept<R>(ctxt: TyCtxt<'_>, f: impl FnOnce(&mut Kept) -> R) -> Option<R> {
+    KEPT.with(|kept| {
+        let mut kept = kept.borrow_mut();
+        let current = kept.as_ref().map(|kept| ptr::eq(kept.session.as_ptr(), ctxt.sess.source_map()));
+        match current {
+            Some(true) => kept.as_mut().map(f),
+            Some(false) => {
+                if kept.as_ref().is_some_and(|kept| kept.session.strong_count() == 0) {
+                    *kept = None;
+                }
+                None
+            }
+            None => None,
+        }
+    })
+}
+
+/// Installs the providers of this module, for use as [`rustc_interface::Config::override_queries`].
+///
+/// [`CompilerConfig::with_borrowck_facts`](crate::CompilerConfig::with_borrowck_facts) does this,
+/// but compilations configured some other way need it too for the adapter to see THIR, the MIR
+/// of [`mir_built`](TyCtxt::mir_built) and borrowck facts.
+pub fn override_queries(session: &Session, providers: &mut Providers) {
+    override_thir_queries(session, providers);
+    providers.mir_borrowck = mir_borrowck;
+}
+
+/// Installs the providers that keep THIR and the MIR of [`mir_built`](TyCtxt::mir_built),
+/// but not the one for borrowck facts, which runs borrowck a second time for every body.
+/// [`CompilerConfig::with_thir`](crate::CompilerConfig::with_thir) does this.
+pub fn override_thir_queries(session: &Session, providers: &mut Providers) {
+    // Whatever an earlier session on this thread kept is of no use to this one.
+    KEPT.with(|kept| {
+        *kept.borrow_mut() = Some(Kept {
+            session: Lrc::downgrade(&session.parse_sess.clone_source_map()),
+            built_mir: Default::default(),
+            thir: Default::default(),
+            borrowck_facts: Default::default(),
+        });
+    });
+    providers.mir_built = mir_built;
+    providers.thir_body = thir_body;
+}
+
+/// Whether the session of `ctxt` installed the providers of this module that keep THIR and built MIR.
+fn keeps_thir(ctxt: TyCtxt<'_>) -> bool {
+    let providers = &ctxt.query_system.fns.local_providers;
+    providers.mir_built as usize == mir_built as usize && providers.thir_body as usize == thir_body as usize
+}
+
+/// Whether the session of `ctxt` installed the provider of this module that keeps borrowck facts.
+fn keeps_borrowck_facts(ctxt: TyCtxt<'_>) -> bool {
+    ctxt.query_system.fns.local_providers.mir_borrowck as usize == mir_borrowck as usize
+}
+
+fn default_providers() -> query::Providers {
+    let mut providers = query::Providers::default();
+    rustc_mir_build::provide(&mut providers);
+    rustc_borrowck::provide(&mut providers);
+    providers
+}
+
+fn mir_built(ctxt: TyCtxt<'_>, def_id: LocalDefId) -> &Steal<Body<'_>> {
+    let body = (default_providers().mir_built)(ctxt, def_id);
+
+    let copy: &Body<'_> = ctxt.arena.alloc(body.borrow().clone());
+    // SAFETY: `copy` lives as long as the arena of `ctxt`, and `with_kept` only hands it out
+    // to the session of `ctxt`, with `built_mir` giving the lifetime back before that.
+    let copy: &'static Body<'static> = unsafe { std::mem::transmute(copy) };
+    with_kept(ctxt, |kept| kept.built_mir.insert(def_id, copy));
+    body
+}
+
+/// The MIR of `def_id` as it was built from THIR, even after it's been stolen.
+/// `None` if the session of `ctxt` didn't install the query providers of this module.
+pub(crate) fn built_mir<'tcx>(ctxt: TyCtxt<'tcx>, def_id: LocalDefId) -> Option<&'tcx Body<'tcx>> {
+    if !keeps_thir(ctxt) {
+        return None;
+    }
+    // Builds the MIR if that didn't happen yet. Once built, it's never built again.
+    let _ = ctxt.mir_built(def_id);
+    let body = with_kept(ctxt, |kept| kept.built_mir.get(&def_id).copied())??;
+    // SAFETY: the body was allocated in the arena of `ctxt`, see `mir_built`.
+    Some(unsafe { std::mem::transmute::<&'static Body<'static>, &'tcx Body<'tcx>>(body) })
+}
+
+fn thir_body(
+    ctxt: TyCtxt<'_>,
+    def_id: LocalDefId,
+) -> Result<(&Steal<Thir<'_>>, ExprId), ErrorGuaranteed> {
+    let (thir, expr) = (default_providers().thir_body)(ctxt, def_id)?;
+
+    // The copy is never stolen, it's only in a `Steal` because that's what the arena holds THIR in.
+    let copy: &Steal<Thir<'_>> = ctxt.arena.alloc(Steal::new(thir.borrow().clone()));
+    // SAFETY: see `mir_built`.
+    let copy: &'static Steal<Thir<'static>> = unsafe { std::mem::transmute(copy) };
+    with_kept(ctxt, |kept| kept.thir.insert(def_id, (copy, expr)));
+    Ok((thir, expr))
+}
+
+/// Calls `f` with the THIR of `def_id` and the expression of its body, even after it's been
+/// stolen. `None` if the body has errors or the session of `ctxt` didn't install the query
+/// providers of this module.
+pub(crate) fn with_thir<'tcx, R>(
+    ctxt: TyCtxt<'tcx>,
+    def_id: LocalDefId,
+    f: impl FnOnce(&Thir<'tcx>, ExprId) -> R,
+) -> Option<R> {
+    if !keeps_thir(ctxt) {
+        return None;
+    }
+    ctxt.thir_body(def_id).ok()?;
+    let (thir, expr) = with_kept(ctxt, |kept| kept.thir.get(&def_id).copied())??;
+    // SAFETY: the THIR was allocated in the arena of `ctxt`, see `thir_body`.
+    let thir = unsafe { std::mem::transmute::<&'static Steal<Thir<'static>>, &'tcx Steal<Thir<'tcx>>>(thir) };
+    Some(f(&thir.borrow(), expr))
+}
+
+fn mir_borrowck(ctxt: TyCtxt<'_>, def_id: LocalDefId) -> &BorrowCheckResult<'_> {
+    let result = (default_providers().mir_borrowck)(ctxt, def_id);
+    // Running borrowck again would report its errors again.
+    if result.tainted_by_errors.is_none() {
+        let facts = get_body_with_borrowck_facts(ctxt, def_id, ConsumerOptions::RegionInferenceContext);
+        // SAFETY: the facts only refer to data in the arenas of `ctxt`, see `mir_built`.
+        let facts: BodyWithBorrowckFacts<'static> = unsafe { std::mem::transmute(facts) };
+        with_kept(ctxt, |kept| kept.borrowck_facts.insert(def_id, Rc::new(facts)));
+    }
+    result
+}
+
+/// What borrowck found out about `def_id`, running it if it didn't run yet.
+/// `None` if borrowck found errors, or if the session of `ctxt` didn't install [`override_queries`].
+pub(crate) fn borrowck_facts<'tcx>(ctxt: TyCtxt<'tcx>, def_id: LocalDefId) -> Option<&'tcx BodyWithBorrowckFacts<'tcx>> {
+    if !keeps_borrowck_facts(ctxt) {
+        return None;
+    }
+    let _ = ctxt.mir_borrowck(def_id);
+    let facts = with_kept(ctxt, |kept| kept.borrowck_facts.get(&def_id).map(Rc::as_ptr))??;
+    // SAFETY: borrowck runs once per body, so the facts stay in the thread local, unmoved, until
+    // the session is over, and they only refer to data in the arenas of `ctxt`, see `mir_built`.
+    Some(unsafe { std::mem::transmute::<&BodyWithBorrowckFacts<'static>, &'tcx BodyWithBorrowckFacts<'tcx>>(&*facts) })
+}
+
+#[cfg(test)]
+mod tests {
+    use rustc_interface::run_compiler;
+    use rustc_middle::ty::TyCtxt;
+    use rustc_span::def_id::LocalDefId;
+
+    use crate::compiler_config::CompilerConfig;
+
+    use super::{borrowck_facts, built_mir, with_thir};
+
+    fn main_fn(ctxt: TyCtxt<'_>) -> LocalDefId {
+        ctxt.entry_fn(()).unwrap().0.expect_local()
+    }
+
+    #[test]
+    fn kept_per_session() {
+        let config = CompilerConfig::new("main.rs", "fn main() { let _ = 1 + 1; }").with_borrowck_facts();
+        run_compiler(config.into(), |compiler| {
+            compiler.enter(|queries| {
+                queries.global_ctxt().unwrap().enter(|ctxt| {
+                    let def_id = main_fn(ctxt);
+                    assert!(built_mir(ctxt, def_id).is_some());
+                    assert!(with_thir(ctxt, def_id, |_, _| ()).is_some());
+                    assert!(borrowck_facts(ctxt, def_id).is_some());
+                })
+            })
+        });
+
+        let config = CompilerConfig::new("main.rs", "fn main() { let _ = 1 + 1; }");
+        run_compiler(config.into(), |compiler| {
+            compiler.enter(|queries| {
+                queries.global_ctxt().unwrap().enter(|ctxt| {
+                    let def_id = main_fn(ctxt);
+                    assert!(built_mir(ctxt, def_id).is_none());
+                    assert!(with_thir(ctxt, def_id, |_, _| ()).is_none());
+                    assert!(borrowck_facts(ctxt, def_id).is_none());
+                })
+            })
+        });
+    }
+}
@@ -1,14 +1,20 @@
-use std::{process, str};
-
+use rustc_attr::{StabilityLevel, StableSince};
+use rustc_session::{filesearch, RustcVersion};
 
+/// The sysroot of the toolchain whose `rustc_driver` this process runs on, so the standard
+/// library that's linked against matches the compiler that's loaded.
 pub fn get_sysroot() -> String {
-    let out = process::Command::new("rustc")
-        .arg("--print=sysroot")
-        .current_dir(".")
-        .output()
-        .expect("TODO: Handle error");
-    str::from_utf8(&out.stdout)
-        .expect("TODO: Handle error")
-        .trim()
-        .into()
+    let sysroot = filesearch::get_or_default_sysroot()
+        .unwrap_or_else(|error| panic!("couldn't find the sysroot: {error}"));
+    sysroot.to_string_lossy().into_owned()
+}
+
+/// The release a stable level was stabilized in, with the upcoming release
+/// standing in for `CURRENT_RUSTC_VERSION`.
+pub(crate) fn stable_since(level: StabilityLevel) -> Option<RustcVersion> {
+    match level {
+        StabilityLevel::Stable { since: StableSince::Version(version), .. } => Some(version),
+        StabilityLevel::Stable { since: StableSince::Current, .. } => Some(RustcVersion::CURRENT),
+        StabilityLevel::Stable { since: StableSince::Err, .. } | StabilityLevel::Unstable { .. } => None,
+    }
 }